@@ -1,6 +1,31 @@
+export * from "./types/AntConfig";
 export * from "./types/AntDto";
+export * from "./types/CasteConfig";
+export * from "./types/CasteDto";
+export * from "./types/CastesConfig";
+export * from "./types/ColonyConfig";
+export * from "./types/FatigueConfig";
 export * from "./types/FoodSourceDto";
+export * from "./types/HungerConfig";
+export * from "./types/MovementConfig";
+export * from "./types/NavigationConfig";
 export * from "./types/NestDto";
+export * from "./types/PheromoneConfig";
+export * from "./types/PositionDto";
+export * from "./types/ProfileDto";
+export * from "./types/RecruitmentConfig";
+export * from "./types/RoleDto";
+export * from "./types/RolesConfig";
+export * from "./types/RunSummary";
+export * from "./types/SimulationConfig";
+export * from "./types/SimulationErrorDto";
+export * from "./types/SimulationEvent";
 export * from "./types/SimulationOptions";
 export * from "./types/StatsDto";
+export * from "./types/StepSummary";
+export * from "./types/StopCondition";
+export * from "./types/SystemProfileDto";
+export * from "./types/TandemRoleDto";
+export * from "./types/TrophallaxisConfig";
+export * from "./types/WorldConfig";
 export * from "./types/WorldDto";
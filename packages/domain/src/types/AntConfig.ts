@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type AntConfig = { arrivalDistance: number, 
+/**
+ * Most food an ant picks up in one trip unless its caste overrides it. Like the
+ * caste override, it is read once when the ant hatches.
+ */
+foodPayloadAmount: number, discoveryRadius: number, minHealth: number, maxHealth: number, deathAnimationTicks: number, 
+/**
+ * Whether foragers remember where they last found food and go back there.
+ */
+routeMemory: boolean, 
+/**
+ * Fruitless trips to a remembered food source before the ant forgets it.
+ */
+maxFailedVisits: number, };
\ No newline at end of file
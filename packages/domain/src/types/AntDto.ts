@@ -1,4 +1,15 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { AntStateDto } from "./AntStateDto";
+import type { CasteDto } from "./CasteDto";
+import type { RoleDto } from "./RoleDto";
+import type { TandemRoleDto } from "./TandemRoleDto";
 
-export type AntDto = { id: number, x: number, y: number, state: AntStateDto, health: number, };
\ No newline at end of file
+export type AntDto = { id: number, x: number, y: number, state: AntStateDto, health: number, age: number, caste: CasteDto, satiety: number, carriedFood: number, stamina: number, 
+/**
+ * The ant's part in a tandem run, if it is in one.
+ */
+tandemRole: TandemRoleDto | null, 
+/**
+ * Only workers take on roles; soldiers and scouts keep to their caste's job.
+ */
+role: RoleDto | null, };
\ No newline at end of file
@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type AntStateDto = { "type": "wandering" } | { "type": "foraging" } | { "type": "returningToNest" } | { "type": "dying", "ticks": number };
\ No newline at end of file
+export type AntStateDto = { "type": "wandering" } | { "type": "foraging" } | { "type": "returningToNest" } | { "type": "hungry" } | { "type": "returningToFood" } | { "type": "following" } | { "type": "resting" } | { "type": "dying", "ticks": number };
\ No newline at end of file
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type CasteConfig = { 
+/**
+ * Relative share of new ants that belong to this caste.
+ */
+spawnWeight: number, speed?: number, minHealth?: number, maxHealth?: number, 
+/**
+ * Read once when an ant hatches, so changing it only affects ants hatched later.
+ */
+carryCapacity?: number, discoveryRadius?: number, wanderProbability?: number, };
\ No newline at end of file
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CasteConfig } from "./CasteConfig";
+
+export type CastesConfig = { worker: CasteConfig, soldier: CasteConfig, scout: CasteConfig, 
+/**
+ * Soldiers turn back towards the nest once they are this far from it.
+ */
+soldierPatrolRadius: number, };
\ No newline at end of file
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ColonyConfig = { 
+/**
+ * Ticks the queen lives for.
+ */
+queenHealth: number, 
+/**
+ * The queen lays at most one egg every this many ticks.
+ */
+eggIntervalTicks: number, 
+/**
+ * Food taken from the nest store for each egg. No egg is laid without it.
+ */
+eggFoodCost: number, eggTicks: number, 
+/**
+ * Minimum ticks spent as a larva.
+ */
+larvaTicks: number, 
+/**
+ * Food a larva must be fed, one unit per tick, before it can pupate.
+ */
+larvaFood: number, pupaTicks: number, };
\ No newline at end of file
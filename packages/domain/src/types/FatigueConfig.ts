@@ -0,0 +1,35 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type FatigueConfig = { 
+/**
+ * Whether ants tire and go home to rest.
+ */
+fatigue: boolean, 
+/**
+ * Stamina of a newly hatched or fully rested ant.
+ */
+maxStamina: number, 
+/**
+ * Stamina lost per unit of distance walked.
+ */
+walkingDrain: number, 
+/**
+ * Extra stamina lost per tick while carrying food.
+ */
+carryingDrain: number, 
+/**
+ * Stamina regained every tick spent resting in the nest.
+ */
+recoveryRate: number, 
+/**
+ * Ants below this stamina are exhausted: they slow down and head home to rest.
+ */
+exhaustedThreshold: number, 
+/**
+ * Resting ants get up again once they have this much stamina.
+ */
+restedThreshold: number, 
+/**
+ * Exhausted ants move at this fraction of their normal speed.
+ */
+exhaustedSpeed: number, };
\ No newline at end of file
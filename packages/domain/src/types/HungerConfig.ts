@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type HungerConfig = { 
+/**
+ * Satiety of a newly hatched or fully fed ant.
+ */
+maxSatiety: number, 
+/**
+ * Satiety lost every tick, whatever the ant is doing.
+ */
+baseDrain: number, 
+/**
+ * Satiety lost per unit of distance walked.
+ */
+walkingDrain: number, 
+/**
+ * Extra satiety lost per tick while carrying food.
+ */
+carryingDrain: number, 
+/**
+ * Wandering ants below this satiety go back to the nest to eat.
+ */
+hungryThreshold: number, 
+/**
+ * Satiety restored by each unit of food eaten from the nest store.
+ */
+satietyPerFood: number, };
\ No newline at end of file
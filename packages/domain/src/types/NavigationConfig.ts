@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type NavigationConfig = { 
+/**
+ * Steer homeward ants by their own noisy homing vector instead of straight at the nest.
+ */
+pathIntegration: boolean, 
+/**
+ * Largest error in radians added to the direction of each step.
+ */
+angularError: number, 
+/**
+ * Largest error added to the length of each step, as a fraction of it. Must be
+ * below 1 so a step is never lost or reversed.
+ */
+distanceError: number, 
+/**
+ * How far from where it thinks the nest is an ant searches before turning back.
+ */
+searchRadius: number, };
\ No newline at end of file
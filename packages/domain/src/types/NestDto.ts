@@ -1,3 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type NestDto = { x: number, y: number, };
\ No newline at end of file
+export type NestDto = { x: number, y: number, foodStore: number, eggs: number, larvae: number, pupae: number, 
+/**
+ * Remaining ticks of the queen's life, or `None` once she has died.
+ */
+queenHealth: number | null, };
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SystemProfileDto } from "./SystemProfileDto";
+
+export type ProfileDto = { ticks: number, tickMeanMicros: number, systems: Array<SystemProfileDto>, };
\ No newline at end of file
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type RecruitmentConfig = { 
+/**
+ * Whether foragers heading back to a remembered source lead a nestmate there.
+ */
+tandemRuns: boolean, 
+/**
+ * Leaders recruit within this distance of the nest, picking the nearest idle ant
+ * within the same distance of themselves.
+ */
+recruitmentRadius: number, 
+/**
+ * Leaders wait while their follower is further behind than this.
+ */
+maxGap: number, };
\ No newline at end of file
@@ -0,0 +1,25 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type RolesConfig = { 
+/**
+ * Whether newly emerged workers stay around the nest as nurses before they forage.
+ * Nurses only differ in where they go; brood develops the same without them.
+ */
+agePolyethism: boolean, 
+/**
+ * Age in ticks at which nurses become foragers.
+ */
+foragerAge: number, 
+/**
+ * Youngest age at which a nurse may switch early when foragers are short.
+ */
+earlyForagerAge: number, 
+/**
+ * Nurses switch early while foragers make up less than this share of workers.
+ * Zero turns early switching off.
+ */
+minForagerShare: number, 
+/**
+ * Nurses stay within this distance of the nest.
+ */
+nurseRadius: number, };
\ No newline at end of file
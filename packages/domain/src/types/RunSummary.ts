@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StatsDto } from "./StatsDto";
+import type { StopCondition } from "./StopCondition";
+
+export type RunSummary = { ticksExecuted: number, stopReason: StopCondition, stats: StatsDto, };
\ No newline at end of file
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AntConfig } from "./AntConfig";
+import type { CastesConfig } from "./CastesConfig";
+import type { ColonyConfig } from "./ColonyConfig";
+import type { FatigueConfig } from "./FatigueConfig";
+import type { HungerConfig } from "./HungerConfig";
+import type { MovementConfig } from "./MovementConfig";
+import type { NavigationConfig } from "./NavigationConfig";
+import type { PheromoneConfig } from "./PheromoneConfig";
+import type { RecruitmentConfig } from "./RecruitmentConfig";
+import type { RolesConfig } from "./RolesConfig";
+import type { TrophallaxisConfig } from "./TrophallaxisConfig";
+import type { WorldConfig } from "./WorldConfig";
+
+export type SimulationConfig = { world: WorldConfig, ant: AntConfig, pheromone: PheromoneConfig, movement: MovementConfig, castes: CastesConfig, colony: ColonyConfig, hunger: HungerConfig, navigation: NavigationConfig, recruitment: RecruitmentConfig, roles: RolesConfig, trophallaxis: TrophallaxisConfig, fatigue: FatigueConfig, };
\ No newline at end of file
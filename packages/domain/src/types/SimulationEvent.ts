@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PositionDto } from "./PositionDto";
+
+export type SimulationEvent = { "type": "antHatched", ant: number, position: PositionDto, } | { "type": "eggLaid", egg: number, position: PositionDto, } | { "type": "queenDied", queen: number, position: PositionDto, } | { "type": "antAte", ant: number, amount: number, position: PositionDto, } | { "type": "antStarved", ant: number, position: PositionDto, } | { "type": "antStartedDying", ant: number, position: PositionDto, } | { "type": "antDespawned", ant: number, position: PositionDto, } | { "type": "foodDiscovered", ant: number, antPosition: PositionDto, foodSource: number, foodPosition: PositionDto, } | { "type": "foodPickedUp", ant: number, foodSource: number, amount: number, position: PositionDto, } | { "type": "foodDelivered", ant: number, nest: number, amount: number, position: PositionDto, } | { "type": "antExhausted", ant: number, position: PositionDto, } | { "type": "trophallaxis", donor: number, receiver: number, amount: number, position: PositionDto, } | { "type": "antBecameForager", ant: number, age: number, position: PositionDto, } | { "type": "tandemRunStarted", leader: number, follower: number, position: PositionDto, } | { "type": "tandemRunCompleted", leader: number, follower: number, position: PositionDto, } | { "type": "foodSourceDepleted", foodSource: number, position: PositionDto, } | { "type": "foodSourceSpawned", foodSource: number, amount: number, position: PositionDto, };
\ No newline at end of file
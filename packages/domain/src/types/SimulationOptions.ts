@@ -1,3 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type SimulationOptions = { width: number, height: number, startingAnts: number, startingFoodSources: number, maxFoodSources: number, };
\ No newline at end of file
+export type SimulationOptions = { width: number, height: number, startingAnts: number, startingFoodSources: number, maxFoodSources: number, 
+/**
+ * Seed for the simulation RNG. A random seed is chosen when absent.
+ */
+seed?: number, };
\ No newline at end of file
@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type StatsDto = { aliveAnts: number, deadAnts: number, foodSourceCount: number, foodInNest: number, };
\ No newline at end of file
+export type StatsDto = { tick: number, aliveAnts: number, deadAnts: number, foodSourceCount: number, foodInNest: number, eggs: number, larvae: number, pupae: number, queenAlive: boolean, starvedAnts: number, mealsEaten: number, tandemRunsStarted: number, tandemRunsCompleted: number, nurses: number, foragers: number, trophallaxisTransfers: number, foodShared: number, restingAnts: number, };
\ No newline at end of file
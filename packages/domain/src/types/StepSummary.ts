@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type StepSummary = { ticksRun: number, 
+/**
+ * Simulated time dropped because it would have taken more than
+ * `world.maxTicksPerStep` ticks to catch up.
+ */
+droppedSeconds: number, };
\ No newline at end of file
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type SystemProfileDto = { name: string, lastMicros: number, meanMicros: number, maxMicros: number, 
+/**
+ * Entities the system worked on in its last run.
+ */
+entities: number, };
\ No newline at end of file
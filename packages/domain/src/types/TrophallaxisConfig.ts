@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type TrophallaxisConfig = { 
+/**
+ * Whether hungry ants are fed by nestmates they meet.
+ */
+trophallaxis: boolean, 
+/**
+ * Ants within this distance of each other can exchange food.
+ */
+encounterRadius: number, 
+/**
+ * Ants without a load only share satiety while they have at least this much.
+ */
+donorThreshold: number, 
+/**
+ * Share of the difference in satiety a donor gives up.
+ */
+shareFraction: number, };
\ No newline at end of file
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type WorldConfig = { foodSpawnChance: number, foodSpawnMinDistanceToNest: number, 
+/**
+ * Simulated time that passes in one tick.
+ */
+secondsPerTick: number, 
+/**
+ * Most ticks a single `tick_with_dt` call runs. Whole ticks of time beyond this
+ * are dropped so a long pause does not stall the caller catching up.
+ */
+maxTicksPerStep: number, };
\ No newline at end of file
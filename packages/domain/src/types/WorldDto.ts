@@ -3,4 +3,4 @@ import type { AntDto } from "./AntDto";
 import type { FoodSourceDto } from "./FoodSourceDto";
 import type { NestDto } from "./NestDto";
 
-export type WorldDto = { nest: NestDto, foodSources: Array<FoodSourceDto>, ants: Array<AntDto>, width: number, height: number, };
\ No newline at end of file
+export type WorldDto = { nest: NestDto, foodSources: Array<FoodSourceDto>, ants: Array<AntDto>, width: number, height: number, seed: number, };
\ No newline at end of file
@@ -1,4 +1,6 @@
-use crate::components::world::AntState;
+use crate::components::world::{AntState, Caste, Position, Role};
+use crate::engine::error::SimulationError;
+use crate::engine::stats::Stats;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
@@ -9,6 +11,10 @@ pub enum AntStateDto {
     Wandering,
     Foraging,
     ReturningToNest,
+    Hungry,
+    ReturningToFood,
+    Following,
+    Resting,
     Dying(u32),
 }
 
@@ -18,26 +24,107 @@ impl From<&AntState> for AntStateDto {
             AntState::Wandering => AntStateDto::Wandering,
             AntState::Foraging => AntStateDto::Foraging,
             AntState::ReturningToNest => AntStateDto::ReturningToNest,
+            AntState::Hungry => AntStateDto::Hungry,
+            AntState::ReturningToFood => AntStateDto::ReturningToFood,
+            AntState::Following => AntStateDto::Following,
+            AntState::Resting => AntStateDto::Resting,
             AntState::Dying(ticks) => AntStateDto::Dying(*ticks),
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/CasteDto.ts")]
+#[serde(rename_all = "camelCase")]
+pub enum CasteDto {
+    Worker,
+    Soldier,
+    Scout,
+}
+
+impl From<&Caste> for CasteDto {
+    fn from(caste: &Caste) -> Self {
+        match caste {
+            Caste::Worker => CasteDto::Worker,
+            Caste::Soldier => CasteDto::Soldier,
+            Caste::Scout => CasteDto::Scout,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/RoleDto.ts")]
+#[serde(rename_all = "camelCase")]
+pub enum RoleDto {
+    Nurse,
+    Forager,
+}
+
+impl From<Role> for RoleDto {
+    fn from(role: Role) -> Self {
+        match role {
+            Role::Nurse => RoleDto::Nurse,
+            Role::Forager => RoleDto::Forager,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/TandemRoleDto.ts")]
+#[serde(rename_all = "camelCase")]
+pub enum TandemRoleDto {
+    Leader,
+    Follower,
+}
+
 #[derive(Serialize, Deserialize, TS, PartialEq, Debug, Clone)]
 #[ts(export, export_to = "../../domain/src/types/AntDto.ts")]
+#[serde(rename_all = "camelCase")]
 pub struct AntDto {
     pub id: u32,
     pub x: f32,
     pub y: f32,
     pub state: AntStateDto,
     pub health: u32,
+    pub age: u32,
+    pub caste: CasteDto,
+    pub satiety: f32,
+    pub carried_food: u32,
+    pub stamina: f32,
+    /// The ant's part in a tandem run, if it is in one.
+    pub tandem_role: Option<TandemRoleDto>,
+    /// Only workers take on roles; soldiers and scouts keep to their caste's job.
+    pub role: Option<RoleDto>,
+}
+
+#[derive(Serialize, Deserialize, TS, PartialEq, Debug, Clone, Copy)]
+#[ts(export, export_to = "../../domain/src/types/PositionDto.ts")]
+pub struct PositionDto {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl From<Position> for PositionDto {
+    fn from(position: Position) -> Self {
+        PositionDto {
+            x: position.x,
+            y: position.y,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, TS, PartialEq, Debug, Clone)]
 #[ts(export, export_to = "../../domain/src/types/NestDto.ts")]
+#[serde(rename_all = "camelCase")]
 pub struct NestDto {
     pub x: f32,
     pub y: f32,
+    pub food_store: u32,
+    pub eggs: u32,
+    pub larvae: u32,
+    pub pupae: u32,
+    /// Remaining ticks of the queen's life, or `None` once she has died.
+    pub queen_health: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, TS, PartialEq, Debug, Clone)]
@@ -49,14 +136,75 @@ pub struct FoodSourceDto {
     pub amount: u32,
 }
 
-#[derive(Serialize, Deserialize, TS, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, TS, PartialEq, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 #[ts(export, export_to = "../../domain/src/types/StatsDto.ts")]
 pub struct StatsDto {
+    pub tick: u32,
     pub alive_ants: u32,
     pub dead_ants: u32,
     pub food_source_count: u32,
     pub food_in_nest: u32,
+    pub eggs: u32,
+    pub larvae: u32,
+    pub pupae: u32,
+    pub queen_alive: bool,
+    pub starved_ants: u32,
+    pub meals_eaten: u32,
+    pub tandem_runs_started: u32,
+    pub tandem_runs_completed: u32,
+    pub nurses: u32,
+    pub foragers: u32,
+    pub trophallaxis_transfers: u32,
+    pub food_shared: u32,
+    pub resting_ants: u32,
+}
+
+impl StatsDto {
+    pub fn new(stats: &Stats, tick: u32) -> Self {
+        StatsDto {
+            tick,
+            alive_ants: stats.alive_ants,
+            dead_ants: stats.dead_ants,
+            food_source_count: stats.food_sources,
+            food_in_nest: stats.food_in_nest,
+            eggs: stats.eggs,
+            larvae: stats.larvae,
+            pupae: stats.pupae,
+            queen_alive: stats.queen_alive,
+            starved_ants: stats.starved_ants,
+            meals_eaten: stats.meals_eaten,
+            tandem_runs_started: stats.tandem_runs_started,
+            tandem_runs_completed: stats.tandem_runs_completed,
+            nurses: stats.nurses,
+            foragers: stats.foragers,
+            trophallaxis_transfers: stats.trophallaxis_transfers,
+            food_shared: stats.food_shared,
+            resting_ants: stats.resting_ants,
+        }
+    }
+}
+
+/// Timings for one system over the rolling profile window, in microseconds.
+#[derive(Serialize, Deserialize, TS, PartialEq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "../../domain/src/types/SystemProfileDto.ts")]
+pub struct SystemProfileDto {
+    pub name: String,
+    pub last_micros: f64,
+    pub mean_micros: f64,
+    pub max_micros: f64,
+    /// Entities the system worked on in its last run.
+    pub entities: u32,
+}
+
+#[derive(Serialize, Deserialize, TS, PartialEq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "../../domain/src/types/ProfileDto.ts")]
+pub struct ProfileDto {
+    pub ticks: u32,
+    pub tick_mean_micros: f64,
+    pub systems: Vec<SystemProfileDto>,
 }
 
 #[derive(Serialize, Deserialize, TS, PartialEq, Debug, Clone)]
@@ -68,4 +216,21 @@ pub struct WorldDto {
     pub ants: Vec<AntDto>,
     pub width: f32,
     pub height: f32,
+    pub seed: u32,
+}
+
+#[derive(Serialize, Deserialize, TS, PartialEq, Debug, Clone)]
+#[ts(export, export_to = "../../domain/src/types/SimulationErrorDto.ts")]
+pub struct SimulationErrorDto {
+    pub kind: String,
+    pub message: String,
+}
+
+impl From<&SimulationError> for SimulationErrorDto {
+    fn from(error: &SimulationError) -> Self {
+        SimulationErrorDto {
+            kind: error.kind().to_string(),
+            message: error.to_string(),
+        }
+    }
 }
@@ -1,13 +1,14 @@
 use hecs::Entity;
+use serde::{Deserialize, Serialize};
 
 // Movement related components
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub struct Position {
     pub x: f32,
     pub y: f32,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Velocity {
     pub dx: f32,
     pub dy: f32,
@@ -17,36 +18,142 @@ pub struct Velocity {
 pub struct Target(pub Entity);
 
 // Ant components
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Ant {
     pub health: u32,
+    /// Tick the ant hatched on; ants present when the simulation starts have 0.
+    #[serde(default)]
+    pub birth_tick: u32,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum Caste {
+    /// Forages and carries food home.
+    #[default]
+    Worker,
+    /// Patrols near the nest and never leads or follows a tandem run.
+    Soldier,
+    /// Roams widely and leads nestmates to the food it finds, but is never recruited.
+    Scout,
+}
+
+impl Caste {
+    pub const ALL: [Caste; 3] = [Caste::Worker, Caste::Soldier, Caste::Scout];
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+/// How well fed an ant is. It drains every tick and the ant starves when it reaches 0.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct Satiety(pub f32);
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum AntState {
     Wandering,
     Foraging,
     ReturningToNest,
+    /// Heading back to the nest to eat from its store.
+    Hungry,
+    /// Heading for a remembered food source.
+    ReturningToFood,
+    /// Being led to food by a tandem-run leader, which is the ant's `Target`.
+    Following,
+    /// Recovering stamina in the nest.
+    Resting,
     Dying(u32),
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub struct FoodPayload(pub u32);
 
+/// Where an ant last picked up food, and how many times it has since gone back there
+/// without picking any up.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct RouteMemory {
+    pub position: Position,
+    pub failed_visits: u32,
+}
+
+/// The ant's running estimate of its offset from the nest, built up from its own
+/// movements. `last_position` is where it was when the estimate was last updated.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct HomingVector {
+    pub dx: f32,
+    pub dy: f32,
+    pub last_position: Position,
+}
+
+/// How much more an ant can do before it has to rest.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct Stamina(pub f32);
+
+/// A worker's current job. Workers without one forage.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum Role {
+    /// Stays in and around the nest. The role only changes where the ant goes: brood is
+    /// fed from the nest store and develops the same with or without nurses.
+    Nurse,
+    #[default]
+    Forager,
+}
+
+/// An ant leading a nestmate to its remembered food source.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TandemLeader;
+
+/// An ant being led in a tandem run. Its leader is its `Target`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TandemFollower;
+
+/// Most food an ant can pick up in one trip. Set from the config when the ant hatches
+/// and kept for life, unlike speed and discovery radius, which follow config changes.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct CarryCapacity(pub u32);
+
+// Colony components
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Queen {
+    pub health: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum BroodStage {
+    Egg,
+    Larva,
+    Pupa,
+}
+
+/// A developing ant, kept at the nest until an adult emerges from the pupa.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Brood {
+    pub stage: BroodStage,
+    pub ticks_in_stage: u32,
+    /// Food fed to the brood while it was a larva.
+    pub food_eaten: u32,
+}
+
+impl Brood {
+    pub fn egg() -> Self {
+        Brood {
+            stage: BroodStage::Egg,
+            ticks_in_stage: 0,
+            food_eaten: 0,
+        }
+    }
+}
+
 // Pheromone system components
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct PheromoneDeposit {
     pub strength: f32,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct PheromoneToFood;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct PheromoneToNest;
 
 // Static world components
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Nest {
     pub food_store: u32,
 }
@@ -57,7 +164,16 @@ impl Nest {
     }
 }
 
-#[derive(Debug, PartialEq)]
+impl Default for Nest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct FoodSource {
     pub amount: u32,
+    /// Tick the food source appeared on; sources present when the simulation starts have 0.
+    #[serde(default)]
+    pub birth_tick: u32,
 }
@@ -1,9 +1,20 @@
-use once_cell::sync::Lazy;
+use crate::components::world::Caste;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use ts_rs::TS;
 
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/WorldConfig.ts")]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
 pub struct WorldConfig {
     pub food_spawn_chance: f64,
     pub food_spawn_min_distance_to_nest: f32,
+    /// Simulated time that passes in one tick.
+    pub seconds_per_tick: f32,
+    /// Most ticks a single `tick_with_dt` call runs. Whole ticks of time beyond this
+    /// are dropped so a long pause does not stall the caller catching up.
+    pub max_ticks_per_step: u32,
 }
 
 impl Default for WorldConfig {
@@ -11,18 +22,36 @@ impl Default for WorldConfig {
         Self {
             food_spawn_chance: 0.01,
             food_spawn_min_distance_to_nest: 25.0,
+            seconds_per_tick: 0.1,
+            max_ticks_per_step: 300,
         }
     }
 }
 
-#[derive(Debug)]
+impl WorldConfig {
+    /// `seconds_per_tick` rounded to whole microseconds, the unit `tick_with_dt` counts
+    /// time in.
+    pub fn micros_per_tick(&self) -> u64 {
+        (f64::from(self.seconds_per_tick) * 1_000_000.0).round() as u64
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/AntConfig.ts")]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
 pub struct AntConfig {
     pub arrival_distance: f32,
+    /// Most food an ant picks up in one trip unless its caste overrides it. Like the
+    /// caste override, it is read once when the ant hatches.
     pub food_payload_amount: u32,
     pub discovery_radius: f32,
     pub min_health: u32,
     pub max_health: u32,
     pub death_animation_ticks: u32,
+    /// Whether foragers remember where they last found food and go back there.
+    pub route_memory: bool,
+    /// Fruitless trips to a remembered food source before the ant forgets it.
+    pub max_failed_visits: u32,
 }
 
 impl Default for AntConfig {
@@ -34,11 +63,15 @@ impl Default for AntConfig {
             min_health: 500,
             max_health: 1000,
             death_animation_ticks: 30,
+            route_memory: true,
+            max_failed_visits: 3,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/PheromoneConfig.ts")]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
 pub struct PheromoneConfig {
     pub emit_chance: f64,
     pub initial_strength: f32,
@@ -57,9 +90,12 @@ impl Default for PheromoneConfig {
     }
 }
 
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/MovementConfig.ts")]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
 pub struct MovementConfig {
     pub wander_probability: f64,
+    /// World units per second.
     pub speed: f32,
 }
 
@@ -67,17 +103,1004 @@ impl Default for MovementConfig {
     fn default() -> Self {
         Self {
             wander_probability: 0.1,
-            speed: 3.0,
+            speed: 30.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/RecruitmentConfig.ts")]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct RecruitmentConfig {
+    /// Whether foragers heading back to a remembered source lead a nestmate there.
+    pub tandem_runs: bool,
+    /// Leaders recruit within this distance of the nest, picking the nearest idle ant
+    /// within the same distance of themselves.
+    pub recruitment_radius: f32,
+    /// Leaders wait while their follower is further behind than this.
+    pub max_gap: f32,
+}
+
+impl Default for RecruitmentConfig {
+    fn default() -> Self {
+        Self {
+            tandem_runs: true,
+            recruitment_radius: 15.0,
+            max_gap: 6.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/NavigationConfig.ts")]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct NavigationConfig {
+    /// Steer homeward ants by their own noisy homing vector instead of straight at the nest.
+    pub path_integration: bool,
+    /// Largest error in radians added to the direction of each step.
+    pub angular_error: f32,
+    /// Largest error added to the length of each step, as a fraction of it. Must be
+    /// below 1 so a step is never lost or reversed.
+    pub distance_error: f32,
+    /// How far from where it thinks the nest is an ant searches before turning back.
+    pub search_radius: f32,
+}
+
+impl Default for NavigationConfig {
+    fn default() -> Self {
+        Self {
+            path_integration: false,
+            angular_error: 0.1,
+            distance_error: 0.1,
+            search_radius: 15.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/HungerConfig.ts")]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct HungerConfig {
+    /// Satiety of a newly hatched or fully fed ant.
+    pub max_satiety: f32,
+    /// Satiety lost every tick, whatever the ant is doing.
+    pub base_drain: f32,
+    /// Satiety lost per unit of distance walked.
+    pub walking_drain: f32,
+    /// Extra satiety lost per tick while carrying food.
+    pub carrying_drain: f32,
+    /// Wandering ants below this satiety go back to the nest to eat.
+    pub hungry_threshold: f32,
+    /// Satiety restored by each unit of food eaten from the nest store.
+    pub satiety_per_food: f32,
+}
+
+impl Default for HungerConfig {
+    fn default() -> Self {
+        Self {
+            max_satiety: 100.0,
+            base_drain: 0.02,
+            walking_drain: 0.05,
+            carrying_drain: 0.05,
+            hungry_threshold: 30.0,
+            satiety_per_food: 35.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/FatigueConfig.ts")]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct FatigueConfig {
+    /// Whether ants tire and go home to rest.
+    pub fatigue: bool,
+    /// Stamina of a newly hatched or fully rested ant.
+    pub max_stamina: f32,
+    /// Stamina lost per unit of distance walked.
+    pub walking_drain: f32,
+    /// Extra stamina lost per tick while carrying food.
+    pub carrying_drain: f32,
+    /// Stamina regained every tick spent resting in the nest.
+    pub recovery_rate: f32,
+    /// Ants below this stamina are exhausted: they slow down and head home to rest.
+    pub exhausted_threshold: f32,
+    /// Resting ants get up again once they have this much stamina.
+    pub rested_threshold: f32,
+    /// Exhausted ants move at this fraction of their normal speed.
+    pub exhausted_speed: f32,
+}
+
+impl Default for FatigueConfig {
+    fn default() -> Self {
+        Self {
+            fatigue: true,
+            max_stamina: 100.0,
+            walking_drain: 0.1,
+            carrying_drain: 0.1,
+            recovery_rate: 1.0,
+            exhausted_threshold: 20.0,
+            rested_threshold: 90.0,
+            exhausted_speed: 0.5,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/ColonyConfig.ts")]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct ColonyConfig {
+    /// Ticks the queen lives for.
+    pub queen_health: u32,
+    /// The queen lays at most one egg every this many ticks.
+    pub egg_interval_ticks: u32,
+    /// Food taken from the nest store for each egg. No egg is laid without it.
+    pub egg_food_cost: u32,
+    pub egg_ticks: u32,
+    /// Minimum ticks spent as a larva.
+    pub larva_ticks: u32,
+    /// Food a larva must be fed, one unit per tick, before it can pupate.
+    pub larva_food: u32,
+    pub pupa_ticks: u32,
+}
+
+impl Default for ColonyConfig {
+    fn default() -> Self {
+        Self {
+            queen_health: 20_000,
+            egg_interval_ticks: 5,
+            egg_food_cost: 2,
+            egg_ticks: 30,
+            larva_ticks: 40,
+            larva_food: 8,
+            pupa_ticks: 30,
         }
     }
 }
 
-#[derive(Debug, Default)]
+/// Overrides for one caste. Values left unset fall back to `ant` and `movement`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/CasteConfig.ts")]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct CasteConfig {
+    /// Relative share of new ants that belong to this caste.
+    pub spawn_weight: f32,
+    #[ts(optional)]
+    pub speed: Option<f32>,
+    #[ts(optional)]
+    pub min_health: Option<u32>,
+    #[ts(optional)]
+    pub max_health: Option<u32>,
+    /// Read once when an ant hatches, so changing it only affects ants hatched later.
+    #[ts(optional)]
+    pub carry_capacity: Option<u32>,
+    #[ts(optional)]
+    pub discovery_radius: Option<f32>,
+    #[ts(optional)]
+    pub wander_probability: Option<f64>,
+}
+
+impl Default for CasteConfig {
+    fn default() -> Self {
+        Self {
+            spawn_weight: 1.0,
+            speed: None,
+            min_health: None,
+            max_health: None,
+            carry_capacity: None,
+            discovery_radius: None,
+            wander_probability: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/CastesConfig.ts")]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct CastesConfig {
+    pub worker: CasteConfig,
+    pub soldier: CasteConfig,
+    pub scout: CasteConfig,
+    /// Soldiers turn back towards the nest once they are this far from it.
+    pub soldier_patrol_radius: f32,
+}
+
+impl Default for CastesConfig {
+    fn default() -> Self {
+        Self {
+            worker: CasteConfig {
+                spawn_weight: 0.8,
+                ..CasteConfig::default()
+            },
+            soldier: CasteConfig {
+                spawn_weight: 0.1,
+                speed: Some(20.0),
+                min_health: Some(800),
+                max_health: Some(1500),
+                wander_probability: Some(0.2),
+                ..CasteConfig::default()
+            },
+            scout: CasteConfig {
+                spawn_weight: 0.1,
+                speed: Some(45.0),
+                min_health: Some(400),
+                max_health: Some(800),
+                discovery_radius: Some(45.0),
+                wander_probability: Some(0.3),
+                ..CasteConfig::default()
+            },
+            soldier_patrol_radius: 20.0,
+        }
+    }
+}
+
+/// The values a caste actually runs with, after falling back to the shared config.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CasteTraits {
+    pub speed: f32,
+    pub min_health: u32,
+    pub max_health: u32,
+    pub carry_capacity: u32,
+    pub discovery_radius: f32,
+    pub wander_probability: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/RolesConfig.ts")]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct RolesConfig {
+    /// Whether newly emerged workers stay around the nest as nurses before they forage.
+    /// Nurses only differ in where they go; brood develops the same without them.
+    pub age_polyethism: bool,
+    /// Age in ticks at which nurses become foragers.
+    pub forager_age: u32,
+    /// Youngest age at which a nurse may switch early when foragers are short.
+    pub early_forager_age: u32,
+    /// Nurses switch early while foragers make up less than this share of workers.
+    /// Zero turns early switching off.
+    pub min_forager_share: f64,
+    /// Nurses stay within this distance of the nest.
+    pub nurse_radius: f32,
+}
+
+impl Default for RolesConfig {
+    fn default() -> Self {
+        Self {
+            age_polyethism: true,
+            forager_age: 400,
+            early_forager_age: 100,
+            min_forager_share: 0.3,
+            nurse_radius: 10.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/TrophallaxisConfig.ts")]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct TrophallaxisConfig {
+    /// Whether hungry ants are fed by nestmates they meet.
+    pub trophallaxis: bool,
+    /// Ants within this distance of each other can exchange food.
+    pub encounter_radius: f32,
+    /// Ants without a load only share satiety while they have at least this much.
+    pub donor_threshold: f32,
+    /// Share of the difference in satiety a donor gives up.
+    pub share_fraction: f32,
+}
+
+impl Default for TrophallaxisConfig {
+    fn default() -> Self {
+        Self {
+            trophallaxis: true,
+            encounter_radius: 1.5,
+            donor_threshold: 60.0,
+            share_fraction: 0.5,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, TS)]
+#[ts(export, export_to = "../../domain/src/types/SimulationConfig.ts")]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
 pub struct SimulationConfig {
     pub world: WorldConfig,
     pub ant: AntConfig,
     pub pheromone: PheromoneConfig,
     pub movement: MovementConfig,
+    pub castes: CastesConfig,
+    pub colony: ColonyConfig,
+    pub hunger: HungerConfig,
+    pub navigation: NavigationConfig,
+    pub recruitment: RecruitmentConfig,
+    pub roles: RolesConfig,
+    pub trophallaxis: TrophallaxisConfig,
+    pub fatigue: FatigueConfig,
+}
+
+/// A single invalid value found while validating a [`SimulationConfig`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    Io(String),
+    Parse(String),
+    UnsupportedFormat(String),
+    Invalid(Vec<FieldError>),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "Failed to read config file: {e}"),
+            ConfigError::Parse(e) => write!(f, "Failed to parse config: {e}"),
+            ConfigError::UnsupportedFormat(ext) => {
+                write!(
+                    f,
+                    "Unsupported config format '{ext}', expected toml or json"
+                )
+            }
+            ConfigError::Invalid(errors) => {
+                write!(f, "Invalid config: ")?;
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{error}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+fn caste_key(caste: Caste) -> &'static str {
+    match caste {
+        Caste::Worker => "worker",
+        Caste::Soldier => "soldier",
+        Caste::Scout => "scout",
+    }
+}
+
+impl SimulationConfig {
+    pub fn caste(&self, caste: Caste) -> &CasteConfig {
+        match caste {
+            Caste::Worker => &self.castes.worker,
+            Caste::Soldier => &self.castes.soldier,
+            Caste::Scout => &self.castes.scout,
+        }
+    }
+
+    pub fn caste_traits(&self, caste: Caste) -> CasteTraits {
+        let overrides = self.caste(caste);
+        CasteTraits {
+            speed: overrides.speed.unwrap_or(self.movement.speed),
+            min_health: overrides.min_health.unwrap_or(self.ant.min_health),
+            max_health: overrides.max_health.unwrap_or(self.ant.max_health),
+            carry_capacity: overrides
+                .carry_capacity
+                .unwrap_or(self.ant.food_payload_amount),
+            discovery_radius: overrides
+                .discovery_radius
+                .unwrap_or(self.ant.discovery_radius),
+            wander_probability: overrides
+                .wander_probability
+                .unwrap_or(self.movement.wander_probability),
+        }
+    }
+
+    /// Parse a TOML config, layering any fields it sets over the defaults.
+    pub fn from_toml_str(input: &str) -> Result<Self, ConfigError> {
+        let config: Self = toml::from_str(input).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parse a JSON config, layering any fields it sets over the defaults.
+    pub fn from_json_str(input: &str) -> Result<Self, ConfigError> {
+        let config: Self =
+            serde_json::from_str(input).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Load a config file, picking the format from its `.toml` or `.json` extension.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let input = std::fs::read_to_string(path).map_err(|e| ConfigError::Io(e.to_string()))?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml_str(&input),
+            Some("json") => Self::from_json_str(&input),
+            other => Err(ConfigError::UnsupportedFormat(
+                other.unwrap_or_default().to_string(),
+            )),
+        }
+    }
+
+    /// Apply a partial JSON object over this config, returning the validated result.
+    pub fn with_json_patch(&self, patch: serde_json::Value) -> Result<Self, ConfigError> {
+        let mut merged =
+            serde_json::to_value(self).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        merge_json(&mut merged, patch);
+
+        let config: Self =
+            serde_json::from_value(merged).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check every field for values the systems cannot run with.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut errors = Vec::new();
+
+        check_probability(
+            &mut errors,
+            "world.foodSpawnChance",
+            self.world.food_spawn_chance,
+        );
+        check_non_negative(
+            &mut errors,
+            "world.foodSpawnMinDistanceToNest",
+            self.world.food_spawn_min_distance_to_nest,
+        );
+        check_positive(
+            &mut errors,
+            "world.secondsPerTick",
+            self.world.seconds_per_tick,
+        );
+        if self.world.seconds_per_tick > 0.0 && self.world.micros_per_tick() == 0 {
+            errors.push(FieldError {
+                field: "world.secondsPerTick".to_string(),
+                message: "must be at least 0.000001".to_string(),
+            });
+        }
+        if self.world.max_ticks_per_step == 0 {
+            errors.push(FieldError {
+                field: "world.maxTicksPerStep".to_string(),
+                message: "must be at least 1".to_string(),
+            });
+        }
+
+        check_positive(
+            &mut errors,
+            "ant.arrivalDistance",
+            self.ant.arrival_distance,
+        );
+        check_non_negative(
+            &mut errors,
+            "ant.discoveryRadius",
+            self.ant.discovery_radius,
+        );
+        if self.ant.food_payload_amount == 0 {
+            errors.push(FieldError {
+                field: "ant.foodPayloadAmount".to_string(),
+                message: "must be > 0".to_string(),
+            });
+        }
+        if self.ant.min_health >= self.ant.max_health {
+            errors.push(FieldError {
+                field: "ant.minHealth".to_string(),
+                message: format!(
+                    "must be less than ant.maxHealth ({} >= {})",
+                    self.ant.min_health, self.ant.max_health
+                ),
+            });
+        }
+
+        check_probability(
+            &mut errors,
+            "pheromone.emitChance",
+            self.pheromone.emit_chance,
+        );
+        check_positive(
+            &mut errors,
+            "pheromone.initialStrength",
+            self.pheromone.initial_strength,
+        );
+        check_non_negative(
+            &mut errors,
+            "pheromone.decayAmount",
+            self.pheromone.decay_amount,
+        );
+        check_non_negative(
+            &mut errors,
+            "pheromone.detectionRadius",
+            self.pheromone.detection_radius,
+        );
+
+        check_probability(
+            &mut errors,
+            "movement.wanderProbability",
+            self.movement.wander_probability,
+        );
+        check_non_negative(&mut errors, "movement.speed", self.movement.speed);
+
+        let mut total_spawn_weight = 0.0;
+        for caste in Caste::ALL {
+            let key = caste_key(caste);
+            let overrides = self.caste(caste);
+            let traits = self.caste_traits(caste);
+            total_spawn_weight += overrides.spawn_weight;
+
+            check_non_negative(
+                &mut errors,
+                &format!("castes.{key}.spawnWeight"),
+                overrides.spawn_weight,
+            );
+            if overrides.speed.is_some() {
+                check_non_negative(&mut errors, &format!("castes.{key}.speed"), traits.speed);
+            }
+            if overrides.carry_capacity == Some(0) {
+                errors.push(FieldError {
+                    field: format!("castes.{key}.carryCapacity"),
+                    message: "must be > 0".to_string(),
+                });
+            }
+            if overrides.discovery_radius.is_some() {
+                check_non_negative(
+                    &mut errors,
+                    &format!("castes.{key}.discoveryRadius"),
+                    traits.discovery_radius,
+                );
+            }
+            if overrides.wander_probability.is_some() {
+                check_probability(
+                    &mut errors,
+                    &format!("castes.{key}.wanderProbability"),
+                    traits.wander_probability,
+                );
+            }
+            if (overrides.min_health.is_some() || overrides.max_health.is_some())
+                && traits.min_health >= traits.max_health
+            {
+                errors.push(FieldError {
+                    field: format!("castes.{key}.minHealth"),
+                    message: format!(
+                        "must be less than the caste's max health ({} >= {})",
+                        traits.min_health, traits.max_health
+                    ),
+                });
+            }
+        }
+        if !total_spawn_weight.is_finite() {
+            errors.push(FieldError {
+                field: "castes".to_string(),
+                message: format!(
+                    "spawn weights must add up to a finite number, got {total_spawn_weight}"
+                ),
+            });
+        } else if total_spawn_weight <= 0.0 {
+            errors.push(FieldError {
+                field: "castes".to_string(),
+                message: "at least one caste needs a spawn weight > 0".to_string(),
+            });
+        }
+        check_positive(
+            &mut errors,
+            "castes.soldierPatrolRadius",
+            self.castes.soldier_patrol_radius,
+        );
+        check_positive(&mut errors, "hunger.maxSatiety", self.hunger.max_satiety);
+        check_non_negative(&mut errors, "hunger.baseDrain", self.hunger.base_drain);
+        check_non_negative(
+            &mut errors,
+            "hunger.walkingDrain",
+            self.hunger.walking_drain,
+        );
+        check_non_negative(
+            &mut errors,
+            "hunger.carryingDrain",
+            self.hunger.carrying_drain,
+        );
+        check_non_negative(
+            &mut errors,
+            "hunger.hungryThreshold",
+            self.hunger.hungry_threshold,
+        );
+        check_positive(
+            &mut errors,
+            "hunger.satietyPerFood",
+            self.hunger.satiety_per_food,
+        );
+        check_non_negative(
+            &mut errors,
+            "navigation.angularError",
+            self.navigation.angular_error,
+        );
+        if !(0.0..1.0).contains(&self.navigation.distance_error) {
+            errors.push(FieldError {
+                field: "navigation.distanceError".to_string(),
+                message: format!(
+                    "must be >= 0 and < 1, got {}",
+                    self.navigation.distance_error
+                ),
+            });
+        }
+        check_positive(
+            &mut errors,
+            "navigation.searchRadius",
+            self.navigation.search_radius,
+        );
+        check_non_negative(
+            &mut errors,
+            "recruitment.recruitmentRadius",
+            self.recruitment.recruitment_radius,
+        );
+        check_positive(&mut errors, "recruitment.maxGap", self.recruitment.max_gap);
+        check_probability(
+            &mut errors,
+            "roles.minForagerShare",
+            self.roles.min_forager_share,
+        );
+        check_non_negative(&mut errors, "roles.nurseRadius", self.roles.nurse_radius);
+        check_positive(
+            &mut errors,
+            "trophallaxis.encounterRadius",
+            self.trophallaxis.encounter_radius,
+        );
+        check_non_negative(
+            &mut errors,
+            "trophallaxis.donorThreshold",
+            self.trophallaxis.donor_threshold,
+        );
+        check_probability(
+            &mut errors,
+            "trophallaxis.shareFraction",
+            f64::from(self.trophallaxis.share_fraction),
+        );
+        check_positive(&mut errors, "fatigue.maxStamina", self.fatigue.max_stamina);
+        check_non_negative(
+            &mut errors,
+            "fatigue.walkingDrain",
+            self.fatigue.walking_drain,
+        );
+        check_non_negative(
+            &mut errors,
+            "fatigue.carryingDrain",
+            self.fatigue.carrying_drain,
+        );
+        check_positive(
+            &mut errors,
+            "fatigue.recoveryRate",
+            self.fatigue.recovery_rate,
+        );
+        check_positive(
+            &mut errors,
+            "fatigue.exhaustedSpeed",
+            self.fatigue.exhausted_speed,
+        );
+        if self.fatigue.rested_threshold <= self.fatigue.exhausted_threshold
+            || self.fatigue.rested_threshold > self.fatigue.max_stamina
+        {
+            errors.push(FieldError {
+                field: "fatigue.restedThreshold".to_string(),
+                message: "must be > fatigue.exhaustedThreshold and <= fatigue.maxStamina"
+                    .to_string(),
+            });
+        }
+        if self.roles.early_forager_age > self.roles.forager_age {
+            errors.push(FieldError {
+                field: "roles.earlyForagerAge".to_string(),
+                message: "must be <= roles.foragerAge".to_string(),
+            });
+        }
+        if self.colony.egg_interval_ticks == 0 {
+            errors.push(FieldError {
+                field: "colony.eggIntervalTicks".to_string(),
+                message: "must be > 0".to_string(),
+            });
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(errors))
+        }
+    }
+}
+
+fn merge_json(target: &mut serde_json::Value, patch: serde_json::Value) {
+    match (target, patch) {
+        (serde_json::Value::Object(target), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                match target.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+fn check_probability(errors: &mut Vec<FieldError>, field: &str, value: f64) {
+    if !(0.0..=1.0).contains(&value) {
+        errors.push(FieldError {
+            field: field.to_string(),
+            message: format!("must be between 0 and 1, got {value}"),
+        });
+    }
 }
 
-pub static SIM_CONFIG: Lazy<SimulationConfig> = Lazy::new(SimulationConfig::default);
+fn check_non_negative(errors: &mut Vec<FieldError>, field: &str, value: f32) {
+    if !value.is_finite() || value < 0.0 {
+        errors.push(FieldError {
+            field: field.to_string(),
+            message: format!("must be a finite number >= 0, got {value}"),
+        });
+    }
+}
+
+fn check_positive(errors: &mut Vec<FieldError>, field: &str, value: f32) {
+    if !value.is_finite() || value <= 0.0 {
+        errors.push(FieldError {
+            field: field.to_string(),
+            message: format!("must be a finite number > 0, got {value}"),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert!(SimulationConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_from_toml_str_layers_partial_config_over_defaults() {
+        // 1. Setup
+        let input = r#"
+            [movement]
+            wanderProbability = 0.25
+
+            [pheromone]
+            decayAmount = 2.5
+        "#;
+
+        // 2. Action
+        let config = SimulationConfig::from_toml_str(input).unwrap();
+
+        // 3. Assertion
+        assert_eq!(config.movement.wander_probability, 0.25);
+        assert_eq!(config.movement.speed, MovementConfig::default().speed);
+        assert_eq!(config.pheromone.decay_amount, 2.5);
+        assert_eq!(config.ant, AntConfig::default());
+        assert_eq!(config.world, WorldConfig::default());
+    }
+
+    #[test]
+    fn test_from_json_str_layers_partial_config_over_defaults() {
+        // 1. Setup
+        let input = r#"{ "ant": { "minHealth": 100, "maxHealth": 200 } }"#;
+
+        // 2. Action
+        let config = SimulationConfig::from_json_str(input).unwrap();
+
+        // 3. Assertion
+        assert_eq!(config.ant.min_health, 100);
+        assert_eq!(config.ant.max_health, 200);
+        assert_eq!(config.ant.arrival_distance, 10.0);
+    }
+
+    #[test]
+    fn test_config_round_trips_through_json_and_toml() {
+        let config = SimulationConfig::default();
+
+        let json = serde_json::to_string(&config).unwrap();
+        let toml = toml::to_string(&config).unwrap();
+
+        assert_eq!(SimulationConfig::from_json_str(&json).unwrap(), config);
+        assert_eq!(SimulationConfig::from_toml_str(&toml).unwrap(), config);
+    }
+
+    #[test]
+    fn test_validate_reports_every_invalid_field() {
+        // 1. Setup
+        let input = r#"{
+            "ant": { "minHealth": 1000, "maxHealth": 500 },
+            "pheromone": { "emitChance": 1.5 },
+            "movement": { "wanderProbability": -0.1 }
+        }"#;
+
+        // 2. Action
+        let error = SimulationConfig::from_json_str(input).unwrap_err();
+
+        // 3. Assertion
+        let ConfigError::Invalid(errors) = error else {
+            panic!("Expected a validation error, got {error:?}");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "ant.minHealth",
+                "pheromone.emitChance",
+                "movement.wanderProbability"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_caste_traits_fall_back_to_shared_config() {
+        // 1. Setup
+        let input = r#"{
+            "ant": { "discoveryRadius": 12.0 },
+            "castes": { "soldier": { "speed": 5.0 } }
+        }"#;
+
+        // 2. Action
+        let config = SimulationConfig::from_json_str(input).unwrap();
+        let worker = config.caste_traits(Caste::Worker);
+        let soldier = config.caste_traits(Caste::Soldier);
+
+        // 3. Assertion
+        assert_eq!(worker.speed, config.movement.speed);
+        assert_eq!(worker.discovery_radius, 12.0);
+        assert_eq!(soldier.speed, 5.0);
+        assert_eq!(soldier.discovery_radius, 12.0);
+        assert_eq!(soldier.carry_capacity, config.ant.food_payload_amount);
+    }
+
+    #[test]
+    fn test_validate_reports_invalid_caste_fields() {
+        // 1. Setup
+        let input = r#"{
+            "castes": {
+                "worker": { "spawnWeight": 0.0 },
+                "soldier": { "spawnWeight": 0.0, "wanderProbability": 2.0 },
+                "scout": { "spawnWeight": 0.0, "minHealth": 5000 }
+            }
+        }"#;
+
+        // 2. Action
+        let error = SimulationConfig::from_json_str(input).unwrap_err();
+
+        // 3. Assertion
+        let ConfigError::Invalid(errors) = error else {
+            panic!("Expected a validation error, got {error:?}");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "castes.soldier.wanderProbability",
+                "castes.scout.minHealth",
+                "castes"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_zero_payload_amount_once() {
+        // 1. Setup
+        let mut config = SimulationConfig::default();
+        config.ant.food_payload_amount = 0;
+        config.castes.scout.carry_capacity = Some(0);
+
+        // 2. Action
+        let error = config.validate().unwrap_err();
+
+        // 3. Assertion
+        let ConfigError::Invalid(errors) = error else {
+            panic!("Expected a validation error, got {error:?}");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec!["ant.foodPayloadAmount", "castes.scout.carryCapacity"]
+        );
+    }
+
+    #[test]
+    fn test_validate_requires_distance_error_below_one() {
+        // 1. Setup
+        let mut config = SimulationConfig::default();
+        config.navigation.distance_error = 1.0;
+
+        // 2. Action
+        let error = config.validate().unwrap_err();
+
+        // 3. Assertion
+        let ConfigError::Invalid(errors) = error else {
+            panic!("Expected a validation error, got {error:?}");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["navigation.distanceError"]);
+    }
+
+    #[test]
+    fn test_validate_rejects_ticks_shorter_than_a_microsecond() {
+        // 1. Setup
+        let mut config = SimulationConfig::default();
+        config.world.seconds_per_tick = 1e-7;
+
+        // 2. Action
+        let error = config.validate().unwrap_err();
+
+        // 3. Assertion
+        let ConfigError::Invalid(errors) = error else {
+            panic!("Expected a validation error, got {error:?}");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["world.secondsPerTick"]);
+    }
+
+    #[test]
+    fn test_validate_rejects_spawn_weights_that_overflow() {
+        // 1. Setup
+        let mut config = SimulationConfig::default();
+        config.castes.worker.spawn_weight = f32::MAX;
+        config.castes.soldier.spawn_weight = f32::MAX;
+
+        // 2. Action
+        let error = config.validate().unwrap_err();
+
+        // 3. Assertion
+        let ConfigError::Invalid(errors) = error else {
+            panic!("Expected a validation error, got {error:?}");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["castes"]);
+    }
+
+    #[test]
+    fn test_with_json_patch_only_changes_patched_fields() {
+        // 1. Setup
+        let mut base = SimulationConfig::default();
+        base.movement.speed = 5.0;
+        let patch = serde_json::json!({ "movement": { "wanderProbability": 0.3 } });
+
+        // 2. Action
+        let config = base.with_json_patch(patch).unwrap();
+
+        // 3. Assertion
+        assert_eq!(config.movement.wander_probability, 0.3);
+        assert_eq!(config.movement.speed, 5.0);
+        assert_eq!(config.pheromone, base.pheromone);
+    }
+
+    #[test]
+    fn test_with_json_patch_rejects_invalid_values() {
+        let patch = serde_json::json!({ "world": { "foodSpawnChance": 2.0 } });
+
+        let error = SimulationConfig::default()
+            .with_json_patch(patch)
+            .unwrap_err();
+
+        assert!(matches!(error, ConfigError::Invalid(_)));
+    }
+
+    #[test]
+    fn test_from_toml_str_rejects_unknown_fields() {
+        let error = SimulationConfig::from_toml_str("[movement]\nsped = 4.0").unwrap_err();
+        assert!(matches!(error, ConfigError::Parse(_)));
+    }
+
+    #[test]
+    fn test_from_file_rejects_unsupported_extension() {
+        let path = std::env::temp_dir().join("formicarium-config-test.yaml");
+        std::fs::write(&path, "").unwrap();
+
+        let error = SimulationConfig::from_file(&path).unwrap_err();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(error, ConfigError::UnsupportedFormat("yaml".to_string()));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::engine::config::ConfigError;
+use crate::engine::snapshot::SnapshotError;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimulationError {
+    MissingNest,
+    MissingEntity(u32),
+    DanglingTarget { entity: u32, target: u32 },
+    InvalidOptions(String),
+    InvalidConfig(ConfigError),
+    Snapshot(SnapshotError),
+    UnknownSystem(String),
+    DuplicateSystem(String),
+    InvalidTimeStep(f64),
+    RecordingStartedLate(u32),
+    InvalidCommandLog(String),
+    RewindDisabled,
+    TickOutsideRewindWindow { tick: u32, earliest: u32 },
+    SeekWhileRecording,
+    UnboundedRun,
+}
+
+impl SimulationError {
+    /// A stable, machine-readable name for the error, used by the web client.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SimulationError::MissingNest => "missingNest",
+            SimulationError::MissingEntity(_) => "missingEntity",
+            SimulationError::DanglingTarget { .. } => "danglingTarget",
+            SimulationError::InvalidOptions(_) => "invalidOptions",
+            SimulationError::InvalidConfig(_) => "invalidConfig",
+            SimulationError::Snapshot(_) => "snapshot",
+            SimulationError::UnknownSystem(_) => "unknownSystem",
+            SimulationError::DuplicateSystem(_) => "duplicateSystem",
+            SimulationError::InvalidTimeStep(_) => "invalidTimeStep",
+            SimulationError::RecordingStartedLate(_) => "recordingStartedLate",
+            SimulationError::InvalidCommandLog(_) => "invalidCommandLog",
+            SimulationError::RewindDisabled => "rewindDisabled",
+            SimulationError::TickOutsideRewindWindow { .. } => "tickOutsideRewindWindow",
+            SimulationError::SeekWhileRecording => "seekWhileRecording",
+            SimulationError::UnboundedRun => "unboundedRun",
+        }
+    }
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulationError::MissingNest => write!(f, "Could not find nest in world"),
+            SimulationError::MissingEntity(id) => write!(f, "Entity {id} no longer exists"),
+            SimulationError::DanglingTarget { entity, target } => write!(
+                f,
+                "Entity {entity} targets entity {target}, which has no position"
+            ),
+            SimulationError::InvalidOptions(e) => write!(f, "Invalid simulation options: {e}"),
+            SimulationError::InvalidConfig(e) => write!(f, "{e}"),
+            SimulationError::Snapshot(e) => write!(f, "{e}"),
+            SimulationError::UnknownSystem(name) => write!(f, "No system named '{name}'"),
+            SimulationError::DuplicateSystem(name) => {
+                write!(f, "A system named '{name}' is already scheduled")
+            }
+            SimulationError::InvalidTimeStep(dt) => {
+                write!(f, "Time step must be a finite number >= 0, got {dt}")
+            }
+            SimulationError::RecordingStartedLate(tick) => write!(
+                f,
+                "Recording must start before the first tick, simulation is at tick {tick}"
+            ),
+            SimulationError::InvalidCommandLog(e) => write!(f, "Invalid command log: {e}"),
+            SimulationError::RewindDisabled => write!(f, "Rewind is not enabled"),
+            SimulationError::TickOutsideRewindWindow { tick, earliest } => write!(
+                f,
+                "Tick {tick} is outside the rewind window, the earliest reachable tick is {earliest}"
+            ),
+            SimulationError::SeekWhileRecording => {
+                write!(f, "Cannot seek backwards while a session is being recorded")
+            }
+            SimulationError::UnboundedRun => {
+                write!(f, "A run needs a tick limit among its stop conditions")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+impl From<ConfigError> for SimulationError {
+    fn from(error: ConfigError) -> Self {
+        SimulationError::InvalidConfig(error)
+    }
+}
+
+impl From<SnapshotError> for SimulationError {
+    fn from(error: SnapshotError) -> Self {
+        SimulationError::Snapshot(error)
+    }
+}
@@ -0,0 +1,145 @@
+use crate::components::dto::PositionDto;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use ts_rs::TS;
+
+/// Something that happened during a tick. Entity fields hold the same ids as the DTOs.
+#[derive(Serialize, Deserialize, TS, PartialEq, Debug, Clone)]
+#[ts(export, export_to = "../../domain/src/types/SimulationEvent.ts")]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SimulationEvent {
+    #[serde(rename_all = "camelCase")]
+    AntHatched { ant: u32, position: PositionDto },
+    #[serde(rename_all = "camelCase")]
+    EggLaid { egg: u32, position: PositionDto },
+    #[serde(rename_all = "camelCase")]
+    QueenDied { queen: u32, position: PositionDto },
+    #[serde(rename_all = "camelCase")]
+    AntAte {
+        ant: u32,
+        amount: u32,
+        position: PositionDto,
+    },
+    #[serde(rename_all = "camelCase")]
+    AntStarved { ant: u32, position: PositionDto },
+    #[serde(rename_all = "camelCase")]
+    AntStartedDying { ant: u32, position: PositionDto },
+    #[serde(rename_all = "camelCase")]
+    AntDespawned { ant: u32, position: PositionDto },
+    #[serde(rename_all = "camelCase")]
+    FoodDiscovered {
+        ant: u32,
+        ant_position: PositionDto,
+        food_source: u32,
+        food_position: PositionDto,
+    },
+    #[serde(rename_all = "camelCase")]
+    FoodPickedUp {
+        ant: u32,
+        food_source: u32,
+        amount: u32,
+        position: PositionDto,
+    },
+    #[serde(rename_all = "camelCase")]
+    FoodDelivered {
+        ant: u32,
+        nest: u32,
+        amount: u32,
+        position: PositionDto,
+    },
+    #[serde(rename_all = "camelCase")]
+    AntExhausted { ant: u32, position: PositionDto },
+    #[serde(rename_all = "camelCase")]
+    Trophallaxis {
+        donor: u32,
+        receiver: u32,
+        amount: f32,
+        position: PositionDto,
+    },
+    #[serde(rename_all = "camelCase")]
+    AntBecameForager {
+        ant: u32,
+        age: u32,
+        position: PositionDto,
+    },
+    #[serde(rename_all = "camelCase")]
+    TandemRunStarted {
+        leader: u32,
+        follower: u32,
+        position: PositionDto,
+    },
+    #[serde(rename_all = "camelCase")]
+    TandemRunCompleted {
+        leader: u32,
+        follower: u32,
+        position: PositionDto,
+    },
+    #[serde(rename_all = "camelCase")]
+    FoodSourceDepleted {
+        food_source: u32,
+        position: PositionDto,
+    },
+    #[serde(rename_all = "camelCase")]
+    FoodSourceSpawned {
+        food_source: u32,
+        amount: u32,
+        position: PositionDto,
+    },
+}
+
+/// Most events kept for a caller that never drains them; the oldest are dropped first.
+pub const MAX_PENDING_EVENTS: usize = 10_000;
+
+/// Collects the events raised by systems until the caller drains them.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Events(VecDeque<SimulationEvent>);
+
+impl Events {
+    pub fn push(&mut self, event: SimulationEvent) {
+        if self.0.len() == MAX_PENDING_EVENTS {
+            self.0.pop_front();
+        }
+        self.0.push_back(event);
+    }
+
+    pub fn drain(&mut self) -> Vec<SimulationEvent> {
+        self.0.drain(..).collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SimulationEvent> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_drops_oldest_when_full() {
+        let mut events = Events::default();
+        for food_source in 0..=MAX_PENDING_EVENTS as u32 {
+            events.push(SimulationEvent::FoodSourceDepleted {
+                food_source,
+                position: PositionDto { x: 0.0, y: 0.0 },
+            });
+        }
+
+        let drained = events.drain();
+
+        assert_eq!(drained.len(), MAX_PENDING_EVENTS);
+        assert!(matches!(
+            drained[0],
+            SimulationEvent::FoodSourceDepleted { food_source: 1, .. }
+        ));
+        assert!(events.is_empty());
+    }
+}
@@ -1,3 +1,12 @@
 pub mod config;
+pub mod error;
+pub mod events;
+#[cfg(feature = "profiling")]
+pub mod profile;
+pub mod replay;
+pub mod rewind;
+pub mod run;
+pub mod schedule;
 pub mod simulation;
+pub mod snapshot;
 pub mod stats;
@@ -0,0 +1,107 @@
+use crate::components::dto::{ProfileDto, SystemProfileDto};
+use std::collections::VecDeque;
+use web_time::Duration;
+
+/// Number of most recent ticks the rolling profile is calculated over.
+pub const PROFILE_WINDOW: usize = 60;
+
+struct SystemTimings {
+    name: String,
+    micros: VecDeque<f64>,
+    entities: u32,
+}
+
+/// Rolling per-system timings, recorded by the schedule while the `profiling` feature is on.
+#[derive(Default)]
+pub struct Profiler {
+    tick_micros: VecDeque<f64>,
+    systems: Vec<SystemTimings>,
+}
+
+fn push_sample(samples: &mut VecDeque<f64>, sample: f64) {
+    if samples.len() == PROFILE_WINDOW {
+        samples.pop_front();
+    }
+    samples.push_back(sample);
+}
+
+fn mean(samples: &VecDeque<f64>) -> f64 {
+    if samples.is_empty() {
+        0.0
+    } else {
+        samples.iter().sum::<f64>() / samples.len() as f64
+    }
+}
+
+impl Profiler {
+    pub fn record_system(&mut self, name: &str, elapsed: Duration, entities: u32) {
+        let index = match self.systems.iter().position(|timings| timings.name == name) {
+            Some(index) => index,
+            None => {
+                self.systems.push(SystemTimings {
+                    name: name.to_string(),
+                    micros: VecDeque::with_capacity(PROFILE_WINDOW),
+                    entities: 0,
+                });
+                self.systems.len() - 1
+            }
+        };
+
+        let timings = &mut self.systems[index];
+        push_sample(&mut timings.micros, elapsed.as_secs_f64() * 1_000_000.0);
+        timings.entities = entities;
+    }
+
+    /// Drop the timings of a system that is no longer scheduled.
+    pub fn forget_system(&mut self, name: &str) {
+        self.systems.retain(|timings| timings.name != name);
+    }
+
+    pub fn record_tick(&mut self, elapsed: Duration) {
+        push_sample(&mut self.tick_micros, elapsed.as_secs_f64() * 1_000_000.0);
+    }
+
+    pub fn to_dto(&self) -> ProfileDto {
+        ProfileDto {
+            ticks: self.tick_micros.len() as u32,
+            tick_mean_micros: mean(&self.tick_micros),
+            systems: self
+                .systems
+                .iter()
+                .map(|timings| SystemProfileDto {
+                    name: timings.name.clone(),
+                    last_micros: timings.micros.back().copied().unwrap_or(0.0),
+                    mean_micros: mean(&timings.micros),
+                    max_micros: timings.micros.iter().copied().fold(0.0, f64::max),
+                    entities: timings.entities,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiler_keeps_a_rolling_window() {
+        // 1. Setup
+        let mut profiler = Profiler::default();
+
+        // 2. Action
+        for micros in 0..(PROFILE_WINDOW as u64 + 10) {
+            profiler.record_system("despawn_system", Duration::from_micros(micros), 3);
+            profiler.record_tick(Duration::from_micros(micros));
+        }
+        let dto = profiler.to_dto();
+
+        // 3. Assertion
+        assert_eq!(dto.ticks, PROFILE_WINDOW as u32);
+        assert_eq!(dto.systems.len(), 1);
+        let system = &dto.systems[0];
+        assert_eq!(system.last_micros, (PROFILE_WINDOW + 9) as f64);
+        assert_eq!(system.max_micros, (PROFILE_WINDOW + 9) as f64);
+        assert_eq!(system.entities, 3);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::engine::config::SimulationConfig;
+use crate::engine::error::SimulationError;
+use crate::engine::simulation::{Simulation, SimulationOptions};
+use serde::{Deserialize, Serialize};
+
+/// An external change applied to a simulation between ticks.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Command {
+    UpdateConfig {
+        config: Box<SimulationConfig>,
+    },
+    #[serde(rename_all = "camelCase")]
+    SetSystemEnabled {
+        name: String,
+        enabled: bool,
+    },
+    /// Time passed to `tick_with_dt`, in microseconds. The ticks it ran are replayed
+    /// like any other tick.
+    AdvanceTime {
+        micros: u64,
+    },
+}
+
+/// A command and the number of ticks that had run when it was applied.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub tick: u32,
+    pub command: Command,
+}
+
+/// The state hash recorded at the end of a tick.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Checkpoint {
+    pub tick: u32,
+    pub hash: u64,
+}
+
+/// Everything needed to run a recorded session again: the starting setup, the commands
+/// applied between ticks and periodic state hashes to check the replay against.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandLog {
+    pub options: SimulationOptions,
+    pub config: SimulationConfig,
+    pub disabled_systems: Vec<String>,
+    /// Ticks between checkpoints. 0 only checks the starting state.
+    pub hash_interval: u32,
+    pub ticks: u32,
+    pub commands: Vec<LogEntry>,
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+/// The first checkpoint a replay did not match.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Divergence {
+    pub tick: u32,
+    pub expected: u64,
+    pub actual: u64,
+}
+
+pub struct ReplayReport {
+    /// The replayed simulation, stopped at the divergence if there was one.
+    pub simulation: Simulation,
+    pub ticks_replayed: u32,
+    pub divergence: Option<Divergence>,
+}
+
+impl CommandLog {
+    pub fn to_json(&self) -> Result<String, SimulationError> {
+        serde_json::to_string(self).map_err(|e| SimulationError::InvalidCommandLog(e.to_string()))
+    }
+
+    pub fn from_json(input: &str) -> Result<Self, SimulationError> {
+        serde_json::from_str(input).map_err(|e| SimulationError::InvalidCommandLog(e.to_string()))
+    }
+
+    pub(crate) fn record_command(&mut self, tick: u32, command: Command) {
+        self.commands.push(LogEntry { tick, command });
+    }
+
+    /// Record the end of a tick, taking a checkpoint if one is due.
+    pub(crate) fn record_tick(
+        &mut self,
+        tick: u32,
+        hash: impl FnOnce() -> Result<u64, SimulationError>,
+    ) -> Result<(), SimulationError> {
+        self.ticks = tick;
+        if self.hash_interval > 0 && tick.is_multiple_of(self.hash_interval) {
+            self.checkpoints.push(Checkpoint {
+                tick,
+                hash: hash()?,
+            });
+        }
+        Ok(())
+    }
+
+    /// Run the session again from the start, checking every checkpoint on the way and
+    /// stopping at the first one that does not match. Custom systems are not part of
+    /// the log, so the replay only runs the built-in schedule.
+    pub fn replay(&self) -> Result<ReplayReport, SimulationError> {
+        let mut simulation = Simulation::with_config(self.options, self.config.clone())?;
+        for name in &self.disabled_systems {
+            simulation.set_system_enabled(name, false)?;
+        }
+
+        let mut commands = self.commands.iter().peekable();
+        let mut checkpoints = self.checkpoints.iter().peekable();
+
+        for tick in 0..=self.ticks {
+            if let Some(checkpoint) = checkpoints.next_if(|checkpoint| checkpoint.tick == tick) {
+                let actual = simulation.state_hash()?;
+                if actual != checkpoint.hash {
+                    return Ok(ReplayReport {
+                        simulation,
+                        ticks_replayed: tick,
+                        divergence: Some(Divergence {
+                            tick,
+                            expected: checkpoint.hash,
+                            actual,
+                        }),
+                    });
+                }
+            }
+
+            while let Some(entry) = commands.next_if(|entry| entry.tick == tick) {
+                match &entry.command {
+                    Command::UpdateConfig { config } => {
+                        simulation.update_config((**config).clone())?
+                    }
+                    Command::SetSystemEnabled { name, enabled } => {
+                        simulation.set_system_enabled(name, *enabled)?
+                    }
+                    Command::AdvanceTime { micros } => {
+                        simulation.advance_time(*micros);
+                    }
+                }
+            }
+
+            if tick < self.ticks {
+                simulation.tick()?;
+            }
+        }
+
+        Ok(ReplayReport {
+            simulation,
+            ticks_replayed: self.ticks,
+            divergence: None,
+        })
+    }
+}
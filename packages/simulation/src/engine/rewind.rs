@@ -0,0 +1,91 @@
+use crate::engine::replay::Command;
+use crate::engine::snapshot::SimulationSnapshot;
+use std::collections::VecDeque;
+
+/// A ring buffer of keyframe snapshots taken every `keyframe_interval` ticks. Any tick
+/// between the oldest keyframe and the present can be reached by restoring the nearest
+/// earlier keyframe and ticking forward from it. Config changes, system toggles and
+/// time steps made between ticks are kept alongside and applied again on the way, so
+/// they do not use up keyframes.
+pub struct RewindBuffer {
+    keyframe_interval: u32,
+    max_keyframes: usize,
+    keyframes: VecDeque<SimulationSnapshot>,
+    changes: Vec<(u32, Command)>,
+}
+
+impl RewindBuffer {
+    pub fn new(keyframe_interval: u32, max_keyframes: usize) -> Self {
+        RewindBuffer {
+            keyframe_interval: keyframe_interval.max(1),
+            max_keyframes: max_keyframes.max(1),
+            keyframes: VecDeque::new(),
+            changes: Vec::new(),
+        }
+    }
+
+    pub fn is_keyframe_due(&self, tick: u32) -> bool {
+        tick.is_multiple_of(self.keyframe_interval)
+    }
+
+    /// Store a keyframe, replacing any taken earlier on the same tick and dropping the
+    /// oldest once the buffer is full.
+    pub fn push(&mut self, keyframe: SimulationSnapshot) {
+        if self
+            .keyframes
+            .back()
+            .is_some_and(|latest| latest.current_tick == keyframe.current_tick)
+        {
+            self.keyframes.pop_back();
+        }
+        if self.keyframes.len() == self.max_keyframes {
+            self.keyframes.pop_front();
+        }
+        self.keyframes.push_back(keyframe);
+
+        if let Some(earliest) = self.earliest_tick() {
+            self.changes.retain(|(tick, _)| *tick >= earliest);
+        }
+    }
+
+    /// Remember a change made after `tick` had run.
+    pub fn record_change(&mut self, tick: u32, command: Command) {
+        self.changes.push((tick, command));
+    }
+
+    /// The changes made from `from` to `to`, inclusive, in the order they were made.
+    /// Changes made after `to` belong to a future that is about to be re-simulated and
+    /// are forgotten.
+    pub fn changes_to_replay(&mut self, from: u32, to: u32) -> Vec<(u32, Command)> {
+        self.changes.retain(|(tick, _)| *tick <= to);
+        self.changes
+            .iter()
+            .filter(|(tick, _)| *tick >= from)
+            .cloned()
+            .collect()
+    }
+
+    /// The earliest tick that can still be reached.
+    pub fn earliest_tick(&self) -> Option<u32> {
+        self.keyframes.front().map(|keyframe| keyframe.current_tick)
+    }
+
+    /// The latest keyframe at or before `tick`.
+    pub fn keyframe_for(&self, tick: u32) -> Option<&SimulationSnapshot> {
+        self.keyframes
+            .iter()
+            .rev()
+            .find(|keyframe| keyframe.current_tick <= tick)
+    }
+
+    /// Forget keyframes from a future that is about to be re-simulated.
+    pub fn discard_after(&mut self, tick: u32) {
+        while self
+            .keyframes
+            .back()
+            .is_some_and(|keyframe| keyframe.current_tick > tick)
+        {
+            self.keyframes.pop_back();
+        }
+    }
+}
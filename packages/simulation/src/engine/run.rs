@@ -0,0 +1,74 @@
+use crate::components::dto::StatsDto;
+use crate::engine::stats::Stats;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// A condition that ends a batch of ticks started by `Simulation::run_until`.
+#[derive(Serialize, Deserialize, TS, PartialEq, Debug, Clone, Copy)]
+#[ts(export, export_to = "../../domain/src/types/StopCondition.ts")]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum StopCondition {
+    /// The queen, every adult ant and all brood have died.
+    ColonyExtinct,
+    /// The nest has stored at least `amount` food.
+    FoodInNest { amount: u32 },
+    /// `ticks` ticks have run since the batch started.
+    TickLimit { ticks: u32 },
+}
+
+impl StopCondition {
+    pub fn is_met(&self, stats: &Stats, ticks_executed: u32) -> bool {
+        match *self {
+            StopCondition::ColonyExtinct => {
+                stats.alive_ants == 0 && stats.brood() == 0 && !stats.queen_alive
+            }
+            StopCondition::FoodInNest { amount } => stats.food_in_nest >= amount,
+            StopCondition::TickLimit { ticks } => ticks_executed >= ticks,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, TS, PartialEq, Debug, Clone)]
+#[ts(export, export_to = "../../domain/src/types/RunSummary.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct RunSummary {
+    pub ticks_executed: u32,
+    pub stop_reason: StopCondition,
+    pub stats: StatsDto,
+}
+
+/// The outcome of `Simulation::tick_with_dt`.
+#[derive(Serialize, Deserialize, TS, PartialEq, Debug, Clone, Copy)]
+#[ts(export, export_to = "../../domain/src/types/StepSummary.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct StepSummary {
+    pub ticks_run: u32,
+    /// Simulated time dropped because it would have taken more than
+    /// `world.maxTicksPerStep` ticks to catch up.
+    pub dropped_seconds: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_condition_is_met() {
+        let stats = Stats {
+            alive_ants: 0,
+            food_in_nest: 40,
+            ..Stats::default()
+        };
+
+        assert!(StopCondition::ColonyExtinct.is_met(&stats, 0));
+        let queen_alive = Stats {
+            queen_alive: true,
+            ..stats
+        };
+        assert!(!StopCondition::ColonyExtinct.is_met(&queen_alive, 0));
+        assert!(StopCondition::FoodInNest { amount: 40 }.is_met(&stats, 0));
+        assert!(!StopCondition::FoodInNest { amount: 41 }.is_met(&stats, 0));
+        assert!(StopCondition::TickLimit { ticks: 5 }.is_met(&stats, 5));
+        assert!(!StopCondition::TickLimit { ticks: 5 }.is_met(&stats, 4));
+    }
+}
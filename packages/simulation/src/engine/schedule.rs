@@ -0,0 +1,620 @@
+use crate::engine::config::SimulationConfig;
+use crate::engine::error::SimulationError;
+use crate::engine::events::Events;
+#[cfg(feature = "profiling")]
+use crate::engine::profile::Profiler;
+use crate::engine::simulation::SimulationOptions;
+use crate::engine::stats::Stats;
+use crate::systems::{
+    ant_dying_system, ant_feeding_system, ant_find_food_system, ant_foraging_system,
+    ant_lifecycle_system, ant_returning_system, apply_velocity_system, brood_development_system,
+    despawn_system, enforce_bounds_system, fatigue_system, food_spawn_system, homing_system,
+    hunger_system, nurse_system, path_integration_system, pheromone_decay_system,
+    pheromone_emission_system, pheromone_following_system, queen_system, recruitment_system,
+    route_memory_system, soldier_patrol_system, tandem_run_system, target_movement_system,
+    task_allocation_system, trophallaxis_system, update_world_stats,
+};
+use hecs::World;
+use rand_pcg::Pcg64;
+
+/// The stages of a tick, run in declaration order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    /// Ageing, hatching, dying and spawning food.
+    Lifecycle,
+    /// Ants decide what to do next and change state.
+    Decisions,
+    /// Pheromones are laid down and decay.
+    Pheromones,
+    /// Depleted and dead entities are removed.
+    Cleanup,
+    /// Entities move based on their current state.
+    Movement,
+    /// Simulation-wide statistics are recalculated.
+    Stats,
+}
+
+/// Everything a system may read or change during a tick.
+pub struct SystemContext<'a> {
+    pub world: &'a mut World,
+    pub config: &'a SimulationConfig,
+    pub options: &'a SimulationOptions,
+    pub tick: u32,
+    pub stats: &'a mut Stats,
+    pub events: &'a mut Events,
+    pub rng: &'a mut Pcg64,
+    /// Entities the running system has worked on, reported in the profile. Reset before
+    /// each system runs; built-in systems add the count they return.
+    pub entities: u32,
+}
+
+pub type BoxedSystem = Box<dyn FnMut(&mut SystemContext) -> Result<(), SimulationError> + Send>;
+
+struct ScheduledSystem {
+    name: String,
+    stage: Stage,
+    enabled: bool,
+    run: BoxedSystem,
+}
+
+/// The ordered list of systems run on every tick. Systems run stage by stage and,
+/// within a stage, in the order they were added unless placed with `insert_before`,
+/// `insert_after` or `move_system`.
+pub struct Schedule {
+    systems: Vec<ScheduledSystem>,
+    #[cfg(feature = "profiling")]
+    profiler: Profiler,
+}
+
+impl Schedule {
+    /// A schedule without any systems.
+    pub fn empty() -> Self {
+        Schedule {
+            systems: Vec::new(),
+            #[cfg(feature = "profiling")]
+            profiler: Profiler::default(),
+        }
+    }
+
+    /// Add a system to the end of `stage`. Names must be unique.
+    pub fn add_system(
+        &mut self,
+        stage: Stage,
+        name: impl Into<String>,
+        system: impl FnMut(&mut SystemContext) -> Result<(), SimulationError> + Send + 'static,
+    ) -> Result<(), SimulationError> {
+        let index = self.stage_end(stage);
+        self.insert_at(index, stage, name.into(), Box::new(system))
+    }
+
+    /// Add a system to the same stage as `anchor`, running just before it.
+    pub fn insert_before(
+        &mut self,
+        anchor: &str,
+        name: impl Into<String>,
+        system: impl FnMut(&mut SystemContext) -> Result<(), SimulationError> + Send + 'static,
+    ) -> Result<(), SimulationError> {
+        let index = self.index_of(anchor)?;
+        let stage = self.systems[index].stage;
+        self.insert_at(index, stage, name.into(), Box::new(system))
+    }
+
+    /// Add a system to the same stage as `anchor`, running just after it.
+    pub fn insert_after(
+        &mut self,
+        anchor: &str,
+        name: impl Into<String>,
+        system: impl FnMut(&mut SystemContext) -> Result<(), SimulationError> + Send + 'static,
+    ) -> Result<(), SimulationError> {
+        let index = self.index_of(anchor)?;
+        let stage = self.systems[index].stage;
+        self.insert_at(index + 1, stage, name.into(), Box::new(system))
+    }
+
+    /// Move a system to the end of `stage`, keeping whether it is enabled.
+    pub fn move_system(&mut self, name: &str, stage: Stage) -> Result<(), SimulationError> {
+        let index = self.index_of(name)?;
+        let mut scheduled = self.systems.remove(index);
+        scheduled.stage = stage;
+        let index = self.stage_end(stage);
+        self.systems.insert(index, scheduled);
+        Ok(())
+    }
+
+    /// Take a system out of the schedule. The returned system can be added back
+    /// elsewhere, under the same or another name.
+    pub fn remove_system(&mut self, name: &str) -> Result<BoxedSystem, SimulationError> {
+        let index = self.index_of(name)?;
+        #[cfg(feature = "profiling")]
+        self.profiler.forget_system(name);
+        Ok(self.systems.remove(index).run)
+    }
+
+    fn index_of(&self, name: &str) -> Result<usize, SimulationError> {
+        self.systems
+            .iter()
+            .position(|scheduled| scheduled.name == name)
+            .ok_or_else(|| SimulationError::UnknownSystem(name.to_string()))
+    }
+
+    /// The index just past the last system of `stage`.
+    fn stage_end(&self, stage: Stage) -> usize {
+        self.systems
+            .iter()
+            .position(|scheduled| scheduled.stage > stage)
+            .unwrap_or(self.systems.len())
+    }
+
+    fn insert_at(
+        &mut self,
+        index: usize,
+        stage: Stage,
+        name: String,
+        run: BoxedSystem,
+    ) -> Result<(), SimulationError> {
+        if self.contains(&name) {
+            return Err(SimulationError::DuplicateSystem(name));
+        }
+        self.systems.insert(
+            index,
+            ScheduledSystem {
+                name,
+                stage,
+                enabled: true,
+                run,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.systems.iter().any(|scheduled| scheduled.name == name)
+    }
+
+    pub fn is_enabled(&self, name: &str) -> Option<bool> {
+        self.systems
+            .iter()
+            .find(|scheduled| scheduled.name == name)
+            .map(|scheduled| scheduled.enabled)
+    }
+
+    /// Turn a system on or off without removing it from the schedule.
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> Result<(), SimulationError> {
+        let scheduled = self
+            .systems
+            .iter_mut()
+            .find(|scheduled| scheduled.name == name)
+            .ok_or_else(|| SimulationError::UnknownSystem(name.to_string()))?;
+        scheduled.enabled = enabled;
+        Ok(())
+    }
+
+    /// Names of all systems in the order they run.
+    pub fn system_names(&self) -> impl Iterator<Item = &str> {
+        self.systems.iter().map(|scheduled| scheduled.name.as_str())
+    }
+
+    /// Names of the systems that are currently turned off.
+    pub fn disabled_systems(&self) -> Vec<String> {
+        self.systems
+            .iter()
+            .filter(|scheduled| !scheduled.enabled)
+            .map(|scheduled| scheduled.name.clone())
+            .collect()
+    }
+
+    /// Run every enabled system once, stopping at the first error.
+    pub fn run(&mut self, ctx: &mut SystemContext) -> Result<(), SimulationError> {
+        #[cfg(feature = "profiling")]
+        let tick_start = web_time::Instant::now();
+
+        for scheduled in self
+            .systems
+            .iter_mut()
+            .filter(|scheduled| scheduled.enabled)
+        {
+            ctx.entities = 0;
+            #[cfg(feature = "profiling")]
+            let start = web_time::Instant::now();
+
+            (scheduled.run)(ctx)?;
+
+            #[cfg(feature = "profiling")]
+            self.profiler
+                .record_system(&scheduled.name, start.elapsed(), ctx.entities);
+        }
+
+        #[cfg(feature = "profiling")]
+        self.profiler.record_tick(tick_start.elapsed());
+        Ok(())
+    }
+
+    #[cfg(feature = "profiling")]
+    pub fn profiler(&self) -> &Profiler {
+        &self.profiler
+    }
+}
+
+impl Default for Schedule {
+    /// The built-in systems in the order the simulation has always run them.
+    fn default() -> Self {
+        let mut schedule = Schedule::empty();
+        let mut add = |stage, name: &str, system: BoxedSystem| {
+            schedule
+                .add_system(stage, name, system)
+                .expect("built-in system names are unique");
+        };
+
+        add(
+            Stage::Lifecycle,
+            "ant_lifecycle_system",
+            Box::new(|ctx| {
+                ctx.entities += ant_lifecycle_system(ctx.world);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Lifecycle,
+            "hunger_system",
+            Box::new(|ctx| {
+                ctx.entities += hunger_system(ctx.world, ctx.config, ctx.stats, ctx.events);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Lifecycle,
+            "fatigue_system",
+            Box::new(|ctx| {
+                ctx.entities += fatigue_system(ctx.world, ctx.config, ctx.events);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Lifecycle,
+            "queen_system",
+            Box::new(|ctx| {
+                ctx.entities += queen_system(ctx.world, ctx.config, ctx.tick, ctx.events);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Lifecycle,
+            "brood_development_system",
+            Box::new(|ctx| {
+                ctx.entities +=
+                    brood_development_system(ctx.world, ctx.config, ctx.tick, ctx.events, ctx.rng);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Lifecycle,
+            "task_allocation_system",
+            Box::new(|ctx| {
+                ctx.entities += task_allocation_system(ctx.world, ctx.config, ctx.tick, ctx.events);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Lifecycle,
+            "ant_dying_system",
+            Box::new(|ctx| {
+                ctx.entities += ant_dying_system(ctx.world, ctx.config, ctx.stats, ctx.events);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Lifecycle,
+            "food_spawn_system",
+            Box::new(|ctx| {
+                ctx.entities += food_spawn_system(
+                    ctx.world,
+                    ctx.config,
+                    ctx.options,
+                    ctx.tick,
+                    ctx.events,
+                    ctx.rng,
+                )?;
+                Ok(())
+            }),
+        );
+
+        add(
+            Stage::Decisions,
+            "ant_find_food_system",
+            Box::new(|ctx| {
+                ctx.entities += ant_find_food_system(ctx.world, ctx.config, ctx.events)?;
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Decisions,
+            "ant_foraging_system",
+            Box::new(|ctx| {
+                ctx.entities += ant_foraging_system(ctx.world, ctx.config, ctx.events)?;
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Decisions,
+            "ant_returning_system",
+            Box::new(|ctx| {
+                ctx.entities += ant_returning_system(ctx.world, ctx.config, ctx.events);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Decisions,
+            "ant_feeding_system",
+            Box::new(|ctx| {
+                ctx.entities += ant_feeding_system(ctx.world, ctx.config, ctx.stats, ctx.events)?;
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Decisions,
+            "trophallaxis_system",
+            Box::new(|ctx| {
+                ctx.entities += trophallaxis_system(ctx.world, ctx.config, ctx.stats, ctx.events);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Decisions,
+            "recruitment_system",
+            Box::new(|ctx| {
+                ctx.entities += recruitment_system(ctx.world, ctx.config, ctx.stats, ctx.events);
+                Ok(())
+            }),
+        );
+
+        add(
+            Stage::Pheromones,
+            "pheromone_emission_system",
+            Box::new(|ctx| {
+                ctx.entities += pheromone_emission_system(ctx.world, ctx.config, ctx.rng);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Pheromones,
+            "pheromone_decay_system",
+            Box::new(|ctx| {
+                ctx.entities += pheromone_decay_system(ctx.world, ctx.config);
+                Ok(())
+            }),
+        );
+
+        add(
+            Stage::Cleanup,
+            "despawn_system",
+            Box::new(|ctx| {
+                ctx.entities += despawn_system(ctx.world, ctx.events)?;
+                Ok(())
+            }),
+        );
+
+        add(
+            Stage::Movement,
+            "pheromone_following_system",
+            Box::new(|ctx| {
+                ctx.entities += pheromone_following_system(ctx.world, ctx.config, ctx.rng);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Movement,
+            "soldier_patrol_system",
+            Box::new(|ctx| {
+                ctx.entities += soldier_patrol_system(ctx.world, ctx.config);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Movement,
+            "nurse_system",
+            Box::new(|ctx| {
+                ctx.entities += nurse_system(ctx.world, ctx.config);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Movement,
+            "route_memory_system",
+            Box::new(|ctx| {
+                ctx.entities += route_memory_system(ctx.world, ctx.config);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Movement,
+            "target_movement_system",
+            Box::new(|ctx| {
+                ctx.entities += target_movement_system(ctx.world);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Movement,
+            "tandem_run_system",
+            Box::new(|ctx| {
+                ctx.entities += tandem_run_system(ctx.world, ctx.config);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Movement,
+            "homing_system",
+            Box::new(|ctx| {
+                ctx.entities += homing_system(ctx.world, ctx.config, ctx.rng);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Movement,
+            "apply_velocity_system",
+            Box::new(|ctx| {
+                ctx.entities += apply_velocity_system(ctx.world, ctx.config);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Movement,
+            "enforce_bounds_system",
+            Box::new(|ctx| {
+                ctx.entities +=
+                    enforce_bounds_system(ctx.world, ctx.options.width, ctx.options.height);
+                Ok(())
+            }),
+        );
+        add(
+            Stage::Movement,
+            "path_integration_system",
+            Box::new(|ctx| {
+                ctx.entities += path_integration_system(ctx.world, ctx.config, ctx.rng);
+                Ok(())
+            }),
+        );
+
+        add(
+            Stage::Stats,
+            "update_world_stats",
+            Box::new(|ctx| {
+                ctx.entities += update_world_stats(ctx.world, ctx.stats);
+                Ok(())
+            }),
+        );
+
+        schedule
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noop(_: &mut SystemContext) -> Result<(), SimulationError> {
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_system_runs_after_earlier_stages() {
+        // 1. Setup
+        let mut schedule = Schedule::empty();
+        schedule.add_system(Stage::Stats, "stats", noop).unwrap();
+        schedule
+            .add_system(Stage::Lifecycle, "first", noop)
+            .unwrap();
+        schedule
+            .add_system(Stage::Lifecycle, "second", noop)
+            .unwrap();
+
+        // 2. Action
+        let names: Vec<&str> = schedule.system_names().collect();
+
+        // 3. Assertion
+        assert_eq!(names, vec!["first", "second", "stats"]);
+    }
+
+    #[test]
+    fn test_add_system_rejects_duplicate_names() {
+        let mut schedule = Schedule::default();
+
+        let result = schedule.add_system(Stage::Cleanup, "despawn_system", noop);
+
+        assert_eq!(
+            result,
+            Err(SimulationError::DuplicateSystem(
+                "despawn_system".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_set_enabled_toggles_system_by_name() {
+        // 1. Setup
+        let mut schedule = Schedule::default();
+
+        // 2. Action
+        schedule.set_enabled("food_spawn_system", false).unwrap();
+        let unknown = schedule.set_enabled("missing_system", false);
+
+        // 3. Assertion
+        assert_eq!(schedule.is_enabled("food_spawn_system"), Some(false));
+        assert_eq!(schedule.disabled_systems(), vec!["food_spawn_system"]);
+        assert_eq!(
+            unknown,
+            Err(SimulationError::UnknownSystem("missing_system".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_insert_before_and_after_place_system_next_to_anchor() {
+        // 1. Setup
+        let mut schedule = Schedule::empty();
+        schedule
+            .add_system(Stage::Lifecycle, "first", noop)
+            .unwrap();
+        schedule.add_system(Stage::Lifecycle, "last", noop).unwrap();
+        schedule.add_system(Stage::Stats, "stats", noop).unwrap();
+
+        // 2. Action
+        schedule.insert_before("last", "middle", noop).unwrap();
+        schedule.insert_after("last", "after_last", noop).unwrap();
+        let unknown = schedule.insert_after("missing_system", "orphan", noop);
+        let duplicate = schedule.insert_before("stats", "first", noop);
+
+        // 3. Assertion
+        let names: Vec<&str> = schedule.system_names().collect();
+        assert_eq!(
+            names,
+            vec!["first", "middle", "last", "after_last", "stats"]
+        );
+        assert_eq!(
+            unknown,
+            Err(SimulationError::UnknownSystem("missing_system".to_string()))
+        );
+        assert_eq!(
+            duplicate,
+            Err(SimulationError::DuplicateSystem("first".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_move_system_keeps_stage_order_and_enabled_flag() {
+        // 1. Setup
+        let mut schedule = Schedule::empty();
+        schedule
+            .add_system(Stage::Lifecycle, "moved", noop)
+            .unwrap();
+        schedule
+            .add_system(Stage::Decisions, "decide", noop)
+            .unwrap();
+        schedule.add_system(Stage::Stats, "stats", noop).unwrap();
+        schedule.set_enabled("moved", false).unwrap();
+
+        // 2. Action
+        schedule.move_system("moved", Stage::Movement).unwrap();
+
+        // 3. Assertion
+        let names: Vec<&str> = schedule.system_names().collect();
+        assert_eq!(names, vec!["decide", "moved", "stats"]);
+        assert_eq!(schedule.is_enabled("moved"), Some(false));
+    }
+
+    #[test]
+    fn test_remove_system_returns_it_for_reinsertion() {
+        // 1. Setup
+        let mut schedule = Schedule::default();
+
+        // 2. Action
+        let system = schedule.remove_system("food_spawn_system").unwrap();
+        let missing = schedule.remove_system("food_spawn_system");
+        schedule
+            .insert_before("ant_lifecycle_system", "food_spawn_system", system)
+            .unwrap();
+
+        // 3. Assertion
+        assert!(missing.is_err());
+        assert_eq!(schedule.system_names().next(), Some("food_spawn_system"));
+    }
+}
@@ -1,21 +1,33 @@
-use crate::components::dto::{AntDto, FoodSourceDto, NestDto, StatsDto, WorldDto};
-use crate::components::world::{Ant, AntState, FoodSource, Nest, Position, Velocity};
-use crate::engine::config::SIM_CONFIG;
-use crate::engine::stats::Stats;
-use crate::systems::{
-    ant_dying_system, ant_find_food_system, ant_foraging_system, ant_lifecycle_system,
-    ant_returning_system, apply_velocity_system, despawn_system, enforce_bounds_system,
-    food_spawn_system, pheromone_decay_system, pheromone_emission_system,
-    pheromone_following_system, target_movement_system, update_world_stats,
+#[cfg(feature = "profiling")]
+use crate::components::dto::ProfileDto;
+use crate::components::dto::{AntDto, FoodSourceDto, NestDto, StatsDto, TandemRoleDto, WorldDto};
+use crate::components::world::{
+    Ant, AntState, CarryCapacity, Caste, FoodPayload, FoodSource, Nest, Position, Queen, Role,
+    Satiety, Stamina, TandemFollower, TandemLeader, Velocity,
+};
+use crate::engine::config::SimulationConfig;
+use crate::engine::error::SimulationError;
+use crate::engine::events::{Events, SimulationEvent};
+use crate::engine::replay::{Checkpoint, Command, CommandLog};
+use crate::engine::rewind::RewindBuffer;
+use crate::engine::run::{RunSummary, StepSummary, StopCondition};
+use crate::engine::schedule::{Schedule, SystemContext};
+use crate::engine::snapshot::{
+    SNAPSHOT_VERSION, SimulationSnapshot, SnapshotError, capture_entities, capture_world,
+    restore_world,
 };
+use crate::engine::stats::Stats;
+use crate::systems::{random_caste, update_world_stats};
+use crate::utils::hash::Fnv1aHasher;
 use crate::utils::maths::target_distance_sq;
 use hecs::World;
 use rand::{Rng, SeedableRng};
 use rand_pcg::Pcg64;
 use serde::{Deserialize, Serialize};
+use std::hash::Hasher;
 use ts_rs::TS;
 
-#[derive(Serialize, Deserialize, Debug, Copy, Clone, TS)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, TS)]
 #[ts(export, export_to = "../../domain/src/types/SimulationOptions.ts")]
 #[serde(rename_all = "camelCase")]
 pub struct SimulationOptions {
@@ -24,6 +36,10 @@ pub struct SimulationOptions {
     pub starting_ants: u32,
     pub starting_food_sources: u32,
     pub max_food_sources: u32,
+    /// Seed for the simulation RNG. A random seed is chosen when absent.
+    #[serde(default)]
+    #[ts(optional)]
+    pub seed: Option<u32>,
 }
 
 impl Default for SimulationOptions {
@@ -34,6 +50,7 @@ impl Default for SimulationOptions {
             starting_ants: 50,
             starting_food_sources: 50,
             max_food_sources: 50,
+            seed: None,
         }
     }
 }
@@ -41,20 +58,79 @@ impl Default for SimulationOptions {
 pub struct Simulation {
     world: World,
     options: SimulationOptions,
+    config: SimulationConfig,
     rng: Pcg64,
+    seed: u32,
+    current_tick: u32,
+    pending_micros: u64,
     stats: Stats,
+    events: Events,
+    schedule: Schedule,
+    recording: Option<CommandLog>,
+    rewind: Option<RewindBuffer>,
+}
+
+/// Nest position relative to the centre of the world, which is where ants start.
+const NEST_OFFSET: f32 = 10.0;
+
+fn validate_setup(
+    options: &SimulationOptions,
+    config: &SimulationConfig,
+) -> Result<(), SimulationError> {
+    config.validate()?;
+
+    let is_positive = |value: f32| value.is_finite() && value > 0.0;
+    if !is_positive(options.width) || !is_positive(options.height) {
+        return Err(SimulationError::InvalidOptions(format!(
+            "width and height must be positive, got {}x{}",
+            options.width, options.height
+        )));
+    }
+
+    // Food is placed by rejection sampling, so at least some of the world must lie
+    // far enough from the nest or spawning would never finish.
+    let nest_x = options.width / 2.0 - NEST_OFFSET;
+    let nest_y = options.height / 2.0 - NEST_OFFSET;
+    let farthest_corner_sq = [
+        (0.0, 0.0),
+        (options.width, 0.0),
+        (0.0, options.height),
+        (options.width, options.height),
+    ]
+    .iter()
+    .map(|&(x, y)| target_distance_sq(nest_x, nest_y, x, y))
+    .fold(0.0, f32::max);
+    let wants_food = options.starting_food_sources > 0 || options.max_food_sources > 0;
+    if wants_food && farthest_corner_sq <= config.world.food_spawn_min_distance_to_nest.powi(2) {
+        return Err(SimulationError::InvalidOptions(format!(
+            "a {}x{} world has no room for food {} units from the nest",
+            options.width, options.height, config.world.food_spawn_min_distance_to_nest
+        )));
+    }
+
+    Ok(())
 }
 
 impl Simulation {
-    pub fn new(sim_options: SimulationOptions) -> Self {
+    pub fn new(sim_options: SimulationOptions) -> Result<Self, SimulationError> {
+        Self::with_config(sim_options, SimulationConfig::default())
+    }
+
+    pub fn with_config(
+        sim_options: SimulationOptions,
+        config: SimulationConfig,
+    ) -> Result<Self, SimulationError> {
+        validate_setup(&sim_options, &config)?;
+
         let mut world = World::new();
-        let mut rng = Pcg64::from_rng(&mut rand::rng());
+        let seed = sim_options.seed.unwrap_or_else(|| rand::rng().random());
+        let mut rng = Pcg64::seed_from_u64(seed as u64);
 
         let start_x: f32 = sim_options.width / 2.0;
         let start_y: f32 = sim_options.height / 2.0;
 
-        let nest_pos_x = start_x - 10.0;
-        let nest_pos_y = start_y - 10.0;
+        let nest_pos_x = start_x - NEST_OFFSET;
+        let nest_pos_y = start_y - NEST_OFFSET;
         world.spawn((
             Position {
                 x: nest_pos_x,
@@ -62,6 +138,15 @@ impl Simulation {
             },
             Nest::new(),
         ));
+        world.spawn((
+            Position {
+                x: nest_pos_x,
+                y: nest_pos_y,
+            },
+            Queen {
+                health: config.colony.queen_health,
+            },
+        ));
 
         // Spawn food sources
         for _ in 0..sim_options.starting_food_sources {
@@ -73,18 +158,26 @@ impl Simulation {
                 y = rng.random_range(0.0..sim_options.height);
                 let distance_sq = target_distance_sq(nest_pos_x, nest_pos_y, x, y);
                 // Ensure the food source is not too close to the nest
-                if distance_sq > SIM_CONFIG.world.food_spawn_min_distance_to_nest.powi(2) {
+                if distance_sq > config.world.food_spawn_min_distance_to_nest.powi(2) {
                     break;
                 }
             }
-            world.spawn((Position { x, y }, FoodSource { amount: 100 }));
+            world.spawn((
+                Position { x, y },
+                FoodSource {
+                    amount: 100,
+                    birth_tick: 0,
+                },
+            ));
         }
 
         // Spawn ants to start
         for _ in 0..sim_options.starting_ants {
+            let caste = random_caste(&config, &mut rng);
+            let traits = config.caste_traits(caste);
             let dx = rng.random_range(-1.0..1.0);
             let dy = rng.random_range(-1.0..1.0);
-            let ant_health = rng.random_range(500..1000);
+            let ant_health = rng.random_range(traits.min_health..traits.max_health);
             world.spawn((
                 Position {
                     x: start_x,
@@ -92,72 +185,509 @@ impl Simulation {
                 },
                 Velocity { dx, dy },
                 AntState::Wandering,
-                Ant { health: ant_health },
+                Ant {
+                    health: ant_health,
+                    birth_tick: 0,
+                },
+                caste,
+                Satiety(config.hunger.max_satiety),
+                CarryCapacity(traits.carry_capacity),
+                Stamina(config.fatigue.max_stamina),
             ));
         }
 
-        Self {
+        let mut stats = Stats::default();
+        update_world_stats(&mut world, &mut stats);
+
+        Ok(Self {
             world,
             options: sim_options,
+            config,
             rng,
-            stats: Stats::default(),
+            seed,
+            current_tick: 0,
+            pending_micros: 0,
+            stats,
+            events: Events::default(),
+            schedule: Schedule::default(),
+            recording: None,
+            rewind: None,
+        })
+    }
+
+    pub fn config(&self) -> &SimulationConfig {
+        &self.config
+    }
+
+    /// Number of ticks run since the simulation was created.
+    pub fn current_tick(&self) -> u32 {
+        self.current_tick
+    }
+
+    /// Replace the config used by the systems. Takes effect from the next tick.
+    pub fn update_config(&mut self, config: SimulationConfig) -> Result<(), SimulationError> {
+        validate_setup(&self.options, &config)?;
+        self.record_change(Command::UpdateConfig {
+            config: Box::new(config.clone()),
+        });
+        self.config = config;
+        Ok(())
+    }
+
+    /// Merge a partial JSON config into the current one. Takes effect from the next tick.
+    pub fn patch_config(&mut self, patch: serde_json::Value) -> Result<(), SimulationError> {
+        self.update_config(self.config.with_json_patch(patch)?)
+    }
+
+    /// Advance the simulation by one tick. If a system fails, the systems before it
+    /// have already run and the world is left part way through the tick.
+    pub fn tick(&mut self) -> Result<(), SimulationError> {
+        self.current_tick += 1;
+
+        let mut ctx = SystemContext {
+            world: &mut self.world,
+            config: &self.config,
+            options: &self.options,
+            tick: self.current_tick,
+            stats: &mut self.stats,
+            events: &mut self.events,
+            rng: &mut self.rng,
+            entities: 0,
+        };
+        self.schedule.run(&mut ctx)?;
+
+        if let Some(mut log) = self.recording.take() {
+            let recorded = log.record_tick(self.current_tick, || self.state_hash());
+            self.recording = Some(log);
+            recorded?;
         }
+        if self
+            .rewind
+            .as_ref()
+            .is_some_and(|rewind| rewind.is_keyframe_due(self.current_tick))
+        {
+            self.take_keyframe();
+        }
+        Ok(())
+    }
+
+    /// Keep a keyframe every `keyframe_interval` ticks, up to `max_keyframes` of them, so
+    /// the simulation can be stepped backwards. The window starts at the current tick.
+    pub fn enable_rewind(&mut self, keyframe_interval: u32, max_keyframes: usize) {
+        self.rewind = Some(RewindBuffer::new(keyframe_interval, max_keyframes));
+        self.take_keyframe();
     }
 
-    pub fn tick(&mut self) {
-        // Systems that control lifecycle events
-        ant_lifecycle_system(&mut self.world, &mut self.rng);
-        ant_dying_system(&mut self.world, &mut self.stats);
-        food_spawn_system(
-            &mut self.world,
-            self.options.width,
-            self.options.height,
-            self.options.max_food_sources,
-            &mut self.rng,
+    pub fn disable_rewind(&mut self) {
+        self.rewind = None;
+    }
+
+    /// Go back one tick.
+    pub fn step_back(&mut self) -> Result<(), SimulationError> {
+        let tick =
+            self.current_tick
+                .checked_sub(1)
+                .ok_or(SimulationError::TickOutsideRewindWindow {
+                    tick: 0,
+                    earliest: 0,
+                })?;
+        self.seek(tick)
+    }
+
+    /// Move to `tick`. Earlier ticks are reached by restoring the nearest keyframe and
+    /// re-simulating from it, later ones by ticking forward. Pending events are dropped,
+    /// and entity handles change whenever a keyframe is restored.
+    pub fn seek(&mut self, tick: u32) -> Result<(), SimulationError> {
+        if tick < self.current_tick {
+            if self.recording.is_some() {
+                return Err(SimulationError::SeekWhileRecording);
+            }
+
+            let rewind = self
+                .rewind
+                .as_ref()
+                .ok_or(SimulationError::RewindDisabled)?;
+            let keyframe = rewind
+                .keyframe_for(tick)
+                .ok_or(SimulationError::TickOutsideRewindWindow {
+                    tick,
+                    earliest: rewind.earliest_tick().unwrap_or(self.current_tick),
+                })?
+                .clone();
+            self.restore_keyframe(&keyframe)?;
+            let rewind = self
+                .rewind
+                .as_mut()
+                .ok_or(SimulationError::RewindDisabled)?;
+            rewind.discard_after(keyframe.current_tick);
+            let changes = rewind.changes_to_replay(keyframe.current_tick, tick);
+
+            // Changes made between ticks are applied again at the same point
+            let mut changes = changes.into_iter().peekable();
+            loop {
+                while let Some((_, command)) =
+                    changes.next_if(|(change_tick, _)| *change_tick == self.current_tick)
+                {
+                    self.apply_change(&command)?;
+                }
+                if self.current_tick == tick {
+                    break;
+                }
+                self.tick()?;
+            }
+        }
+
+        while self.current_tick < tick {
+            self.tick()?;
+        }
+        self.events.drain();
+        Ok(())
+    }
+
+    fn take_keyframe(&mut self) {
+        if let Some(mut rewind) = self.rewind.take() {
+            rewind.push(self.snapshot());
+            self.rewind = Some(rewind);
+        }
+    }
+
+    fn restore_keyframe(&mut self, keyframe: &SimulationSnapshot) -> Result<(), SimulationError> {
+        self.world = restore_world(&keyframe.archetypes)?;
+        self.config = keyframe.config.clone();
+        self.rng = keyframe.rng.clone();
+        self.current_tick = keyframe.current_tick;
+        self.pending_micros = keyframe.pending_micros;
+        self.stats = keyframe.stats;
+
+        let names: Vec<String> = self.schedule.system_names().map(String::from).collect();
+        for name in names {
+            let enabled = !keyframe.disabled_systems.contains(&name);
+            self.schedule.set_enabled(&name, enabled)?;
+        }
+        Ok(())
+    }
+
+    /// Advance the simulation by `dt` seconds of simulated time. Runs as many whole ticks
+    /// of `world.secondsPerTick` as fit and carries the remainder over to the next call.
+    /// Time is counted in whole microseconds, so the result is the same however the time
+    /// is split up. At most `world.maxTicksPerStep` ticks run per call; whole ticks
+    /// beyond that are dropped and reported in the summary.
+    pub fn tick_with_dt(&mut self, dt: f64) -> Result<StepSummary, SimulationError> {
+        if !dt.is_finite() || dt < 0.0 {
+            return Err(SimulationError::InvalidTimeStep(dt));
+        }
+
+        let micros = (dt * 1_000_000.0).round() as u64;
+        self.record_change(Command::AdvanceTime { micros });
+        let summary = self.advance_time(micros);
+        for _ in 0..summary.ticks_run {
+            self.tick()?;
+        }
+        Ok(summary)
+    }
+
+    /// Add `micros` to the time carried over and take out the ticks it pays for, without
+    /// running them. The time is settled up front so that a replay, which runs the same
+    /// ticks one by one, carries over the same time at every checkpoint.
+    pub(crate) fn advance_time(&mut self, micros: u64) -> StepSummary {
+        let micros_per_tick = self.config.world.micros_per_tick();
+        let pending = self.pending_micros.saturating_add(micros);
+        let ticks_run =
+            (pending / micros_per_tick).min(u64::from(self.config.world.max_ticks_per_step)) as u32;
+        let pending = pending - u64::from(ticks_run) * micros_per_tick;
+
+        let dropped_micros = pending - pending % micros_per_tick;
+        self.pending_micros = pending - dropped_micros;
+        StepSummary {
+            ticks_run,
+            dropped_seconds: dropped_micros as f64 / 1_000_000.0,
+        }
+    }
+
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Mutable access to the schedule, for registering custom systems.
+    /// Changes made through this are not recorded; use `set_system_enabled` while recording.
+    pub fn schedule_mut(&mut self) -> &mut Schedule {
+        &mut self.schedule
+    }
+
+    /// Turn a scheduled system on or off by name. Takes effect from the next tick.
+    pub fn set_system_enabled(&mut self, name: &str, enabled: bool) -> Result<(), SimulationError> {
+        self.schedule.set_enabled(name, enabled)?;
+        self.record_change(Command::SetSystemEnabled {
+            name: name.to_string(),
+            enabled,
+        });
+        Ok(())
+    }
+
+    /// Note a change made between ticks in the session recording, and for the rewind
+    /// buffer to apply again when re-simulating past it.
+    fn record_change(&mut self, command: Command) {
+        if let Some(log) = &mut self.recording {
+            log.record_command(self.current_tick, command.clone());
+        }
+        if let Some(rewind) = &mut self.rewind {
+            rewind.record_change(self.current_tick, command);
+        }
+    }
+
+    /// Apply a change noted by `record_change` again, without noting it a second time.
+    fn apply_change(&mut self, command: &Command) -> Result<(), SimulationError> {
+        match command {
+            Command::UpdateConfig { config } => self.config = (**config).clone(),
+            Command::SetSystemEnabled { name, enabled } => {
+                self.schedule.set_enabled(name, *enabled)?
+            }
+            Command::AdvanceTime { micros } => {
+                self.advance_time(*micros);
+            }
+        }
+        Ok(())
+    }
+
+    /// Start recording the session into a [`CommandLog`], with a state hash every
+    /// `hash_interval` ticks. Must be called before the first tick.
+    pub fn start_recording(&mut self, hash_interval: u32) -> Result<(), SimulationError> {
+        if self.current_tick != 0 {
+            return Err(SimulationError::RecordingStartedLate(self.current_tick));
+        }
+
+        self.recording = Some(CommandLog {
+            options: SimulationOptions {
+                seed: Some(self.seed),
+                ..self.options
+            },
+            config: self.config.clone(),
+            disabled_systems: self.schedule.disabled_systems(),
+            hash_interval,
+            ticks: 0,
+            commands: Vec::new(),
+            checkpoints: vec![Checkpoint {
+                tick: 0,
+                hash: self.state_hash()?,
+            }],
+        });
+        Ok(())
+    }
+
+    /// Stop recording and hand back the log, if recording was on.
+    pub fn stop_recording(&mut self) -> Option<CommandLog> {
+        self.recording.take()
+    }
+
+    pub fn recording(&self) -> Option<&CommandLog> {
+        self.recording.as_ref()
+    }
+
+    /// A stable hash over every entity and component in entity handle order, the RNG
+    /// state, the tick, the stats, the config, time carried over by `tick_with_dt` and
+    /// the disabled systems. Two simulations with the same hash continue identically as
+    /// long as their custom systems match. Entity handles change on load, and so does
+    /// the hash.
+    pub fn state_hash(&self) -> Result<u64, SimulationError> {
+        let mut hasher = Fnv1aHasher::default();
+        let state = (
+            self.current_tick,
+            &self.stats,
+            &self.rng,
+            &self.config,
+            self.pending_micros,
+            self.schedule.disabled_systems(),
+            capture_entities(&self.world),
         );
+        serde_json::to_writer(&mut hasher, &state)
+            .map_err(|e| SnapshotError::Serialize(e.to_string()))?;
+        Ok(hasher.finish())
+    }
+
+    /// Per-system timings over the last few ticks.
+    #[cfg(feature = "profiling")]
+    pub fn profile(&self) -> ProfileDto {
+        self.schedule.profiler().to_dto()
+    }
+
+    /// Run exactly `ticks` ticks.
+    pub fn run(&mut self, ticks: u32) -> Result<RunSummary, SimulationError> {
+        self.run_until(&[StopCondition::TickLimit { ticks }])
+    }
+
+    /// Tick until any of `conditions` is met. Conditions are checked before every tick,
+    /// so a condition that already holds stops the run without ticking. `conditions`
+    /// must include a `TickLimit` so the run is guaranteed to end.
+    pub fn run_until(
+        &mut self,
+        conditions: &[StopCondition],
+    ) -> Result<RunSummary, SimulationError> {
+        if !conditions
+            .iter()
+            .any(|condition| matches!(condition, StopCondition::TickLimit { .. }))
+        {
+            return Err(SimulationError::UnboundedRun);
+        }
+        let mut ticks_executed: u32 = 0;
+
+        loop {
+            if let Some(condition) = conditions
+                .iter()
+                .find(|condition| condition.is_met(&self.stats, ticks_executed))
+            {
+                return Ok(RunSummary {
+                    ticks_executed,
+                    stop_reason: *condition,
+                    stats: StatsDto::new(&self.stats, self.current_tick),
+                });
+            }
 
-        // Systems that determine decisions and state changes.
-        ant_find_food_system(&mut self.world);
-        ant_foraging_system(&mut self.world);
-        ant_returning_system(&mut self.world);
+            self.tick()?;
+            ticks_executed += 1;
+        }
+    }
+
+    /// Take the events raised since the last call, in the order they happened.
+    pub fn drain_events(&mut self) -> Vec<SimulationEvent> {
+        self.events.drain()
+    }
+
+    /// Capture the complete state of the simulation.
+    pub fn snapshot(&self) -> SimulationSnapshot {
+        SimulationSnapshot {
+            version: SNAPSHOT_VERSION,
+            options: self.options,
+            config: self.config.clone(),
+            seed: self.seed,
+            current_tick: self.current_tick,
+            pending_micros: self.pending_micros,
+            rng: self.rng.clone(),
+            stats: self.stats,
+            disabled_systems: self.schedule.disabled_systems(),
+            archetypes: capture_world(&self.world),
+        }
+    }
 
-        // Pheromone systems that modify the world state.
-        pheromone_emission_system(&mut self.world, &mut self.rng);
-        pheromone_decay_system(&mut self.world);
+    /// Rebuild a simulation that continues exactly where the snapshot was taken.
+    pub fn from_snapshot(snapshot: &SimulationSnapshot) -> Result<Self, SnapshotError> {
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(snapshot.version));
+        }
+        snapshot
+            .config
+            .validate()
+            .map_err(SnapshotError::InvalidConfig)?;
 
-        // Clean up systems that remove entities.
-        despawn_system(&mut self.world);
+        // Custom systems are not part of the snapshot, so only built-in names are applied here.
+        let mut schedule = Schedule::default();
+        for name in &snapshot.disabled_systems {
+            schedule.set_enabled(name, false).ok();
+        }
 
-        // Systems that execute movement based on the current state.
-        pheromone_following_system(&mut self.world, &mut self.rng);
-        target_movement_system(&mut self.world);
+        Ok(Self {
+            world: restore_world(&snapshot.archetypes)?,
+            options: snapshot.options,
+            config: snapshot.config.clone(),
+            rng: snapshot.rng.clone(),
+            seed: snapshot.seed,
+            current_tick: snapshot.current_tick,
+            pending_micros: snapshot.pending_micros,
+            stats: snapshot.stats,
+            events: Events::default(),
+            schedule,
+            recording: None,
+            rewind: None,
+        })
+    }
 
-        // Simulation-wide systems.
-        apply_velocity_system(&mut self.world);
-        enforce_bounds_system(&mut self.world, self.options.width, self.options.height);
-        update_world_stats(&mut self.world, &mut self.stats);
+    /// Serialize the complete state of the simulation to a versioned JSON document.
+    pub fn save(&self) -> Result<String, SnapshotError> {
+        self.snapshot().to_json()
     }
 
-    pub fn get_world_state_dto(&mut self) -> Result<WorldDto, &'static str> {
+    /// Restore a simulation from a document written by [`Simulation::save`].
+    pub fn load(input: &str) -> Result<Self, SnapshotError> {
+        Self::from_snapshot(&SimulationSnapshot::from_json(input)?)
+    }
+
+    pub fn get_world_state_dto(&mut self) -> Result<WorldDto, SimulationError> {
+        let queen_health = self
+            .world
+            .query::<&Queen>()
+            .iter()
+            .next()
+            .map(|(_, queen)| queen.health);
         let nest = self
             .world
             .query::<(&Position, &Nest)>()
             .iter()
             .next()
-            .map(|(_, (pos, _))| NestDto { x: pos.x, y: pos.y })
-            .ok_or("Could not find nest in world")?;
+            .map(|(_, (pos, nest))| NestDto {
+                x: pos.x,
+                y: pos.y,
+                food_store: nest.food_store,
+                eggs: self.stats.eggs,
+                larvae: self.stats.larvae,
+                pupae: self.stats.pupae,
+                queen_health,
+            })
+            .ok_or(SimulationError::MissingNest)?;
 
         let ants = self
             .world
-            .query::<(&Position, &Ant, &AntState)>()
+            .query::<(
+                &Position,
+                &Ant,
+                &AntState,
+                Option<&Caste>,
+                Option<&Satiety>,
+                Option<&FoodPayload>,
+                Option<&TandemLeader>,
+                Option<&TandemFollower>,
+                Option<&Role>,
+                Option<&Stamina>,
+            )>()
             .iter()
-            .map(|(entity, (position, ant, ant_state))| AntDto {
-                id: entity.id(),
-                x: position.x,
-                y: position.y,
-                state: ant_state.into(),
-                health: ant.health,
-            })
+            .map(
+                |(
+                    entity,
+                    (
+                        position,
+                        ant,
+                        ant_state,
+                        caste,
+                        satiety,
+                        payload,
+                        leader,
+                        follower,
+                        role,
+                        stamina,
+                    ),
+                )| {
+                    let caste = caste.copied().unwrap_or_default();
+                    AntDto {
+                        id: entity.id(),
+                        x: position.x,
+                        y: position.y,
+                        state: ant_state.into(),
+                        health: ant.health,
+                        age: self.current_tick.saturating_sub(ant.birth_tick),
+                        caste: (&caste).into(),
+                        satiety: satiety
+                            .map_or(self.config.hunger.max_satiety, |satiety| satiety.0),
+                        carried_food: payload.map_or(0, |payload| payload.0),
+                        stamina: stamina
+                            .map_or(self.config.fatigue.max_stamina, |stamina| stamina.0),
+                        tandem_role: match (leader, follower) {
+                            (Some(_), _) => Some(TandemRoleDto::Leader),
+                            (None, Some(_)) => Some(TandemRoleDto::Follower),
+                            (None, None) => None,
+                        },
+                        role: (caste == Caste::Worker)
+                            .then(|| role.copied().unwrap_or_default().into()),
+                    }
+                },
+            )
             .collect();
 
         let food_sources = self
@@ -178,36 +708,34 @@ impl Simulation {
             ants,
             width: self.options.width,
             height: self.options.height,
+            seed: self.seed,
         })
     }
 
-    pub fn get_world_statistics_dto(&mut self) -> Result<StatsDto, &'static str> {
-        Ok(StatsDto {
-            alive_ants: self.stats.alive_ants,
-            dead_ants: self.stats.dead_ants,
-            food_source_count: self.stats.food_sources,
-            food_in_nest: self.stats.food_in_nest,
-        })
+    pub fn get_world_statistics_dto(&mut self) -> Result<StatsDto, SimulationError> {
+        Ok(StatsDto::new(&self.stats, self.current_tick))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::components::dto::AntStateDto;
     use crate::components::world::{Position, Velocity};
+    use crate::engine::schedule::Stage;
 
     #[test]
     fn test_simulation_tick_updates_position() {
         // 1. Setup
         let params = SimulationOptions::default();
-        let mut simulation = Simulation::new(params);
+        let mut simulation = Simulation::new(params).unwrap();
         let entity = simulation.world.spawn((
             Position { x: 10.0, y: 10.0 },
             Velocity { dx: 5.0, dy: -5.0 },
         ));
 
         // 2. Action
-        simulation.tick();
+        simulation.tick().unwrap();
 
         // 3. Assertion
         let position = simulation.world.get::<&Position>(entity).unwrap();
@@ -215,11 +743,284 @@ mod tests {
         assert_eq!(position.y, 0.0);
     }
 
+    #[test]
+    fn test_simulations_with_different_configs_run_side_by_side() {
+        // 1. Setup
+        let params = SimulationOptions {
+            seed: Some(7),
+            ..SimulationOptions::default()
+        };
+        let mut config = SimulationConfig::default();
+        config.movement.speed = 0.0;
+        config.castes.soldier.spawn_weight = 0.0;
+        config.castes.scout.spawn_weight = 0.0;
+        let mut stationary = Simulation::with_config(params, config).unwrap();
+        let mut moving = Simulation::new(params).unwrap();
+        let start = stationary.get_world_state_dto().unwrap();
+
+        // 2. Action
+        stationary.tick().unwrap();
+        moving.tick().unwrap();
+
+        // 3. Assertion
+        assert_eq!(stationary.config().movement.speed, 0.0);
+        assert_eq!(moving.config().movement.speed, 30.0);
+        let stationary_dto = stationary.get_world_state_dto().unwrap();
+        let moving_dto = moving.get_world_state_dto().unwrap();
+        for (before, after) in start.ants.iter().zip(&stationary_dto.ants) {
+            assert_eq!((before.x, before.y), (after.x, after.y));
+        }
+        assert!(
+            start
+                .ants
+                .iter()
+                .zip(&moving_dto.ants)
+                .any(|(before, after)| (before.x, before.y) != (after.x, after.y))
+        );
+    }
+
+    #[test]
+    fn test_patch_config_applies_on_next_tick() {
+        // 1. Setup
+        let mut simulation = Simulation::new(SimulationOptions::default()).unwrap();
+        let entity = simulation
+            .world
+            .spawn((Position { x: 10.0, y: 10.0 }, Velocity { dx: 1.0, dy: 0.0 }));
+
+        // 2. Action
+        simulation
+            .patch_config(serde_json::json!({ "movement": { "speed": 10.0 } }))
+            .unwrap();
+        simulation.tick().unwrap();
+
+        // 3. Assertion
+        assert_eq!(simulation.config().movement.speed, 10.0);
+        assert_eq!(simulation.world.get::<&Position>(entity).unwrap().x, 11.0);
+    }
+
+    #[test]
+    fn test_update_config_rejects_invalid_config_and_keeps_current() {
+        // 1. Setup
+        let mut simulation = Simulation::new(SimulationOptions::default()).unwrap();
+        let mut config = SimulationConfig::default();
+        config.movement.wander_probability = 1.5;
+
+        // 2. Action
+        let result = simulation.update_config(config);
+
+        // 3. Assertion
+        assert!(result.is_err());
+        assert_eq!(*simulation.config(), SimulationConfig::default());
+    }
+
+    #[test]
+    fn test_loaded_simulation_continues_exactly_where_it_left_off() {
+        // 1. Setup
+        let params = SimulationOptions {
+            seed: Some(99),
+            ..SimulationOptions::default()
+        };
+        let mut original = Simulation::new(params).unwrap();
+        for _ in 0..300 {
+            original.tick().unwrap();
+        }
+
+        // 2. Action
+        let mut restored = Simulation::load(&original.save().unwrap()).unwrap();
+
+        // 3. Assertion
+        // Entity handles are reallocated on load, so compare everything but the ids.
+        for _ in 0..300 {
+            assert_eq!(without_ids(&mut original), without_ids(&mut restored));
+            assert_eq!(
+                original.get_world_statistics_dto().unwrap(),
+                restored.get_world_statistics_dto().unwrap()
+            );
+            original.tick().unwrap();
+            restored.tick().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_simulation_new_rejects_invalid_options() {
+        let params = SimulationOptions {
+            width: 0.0,
+            ..SimulationOptions::default()
+        };
+
+        let result = Simulation::new(params);
+
+        assert!(matches!(result, Err(SimulationError::InvalidOptions(_))));
+    }
+
+    #[test]
+    fn test_simulation_new_rejects_world_too_small_for_food() {
+        let params = SimulationOptions {
+            width: 10.0,
+            height: 10.0,
+            ..SimulationOptions::default()
+        };
+
+        let result = Simulation::new(params);
+
+        assert!(matches!(result, Err(SimulationError::InvalidOptions(_))));
+    }
+
+    #[test]
+    fn test_tick_returns_error_when_nest_is_missing() {
+        // 1. Setup
+        let mut simulation = Simulation::new(SimulationOptions::default()).unwrap();
+        let nest = simulation
+            .world
+            .query::<&Nest>()
+            .iter()
+            .next()
+            .map(|(entity, _)| entity)
+            .unwrap();
+        simulation.world.despawn(nest).unwrap();
+
+        // 2. Action & Assertion
+        assert_eq!(simulation.tick(), Err(SimulationError::MissingNest));
+        assert_eq!(
+            simulation.get_world_state_dto(),
+            Err(SimulationError::MissingNest)
+        );
+    }
+
+    #[test]
+    fn test_drain_events_returns_events_once() {
+        // 1. Setup
+        let params = SimulationOptions {
+            seed: Some(3),
+            ..SimulationOptions::default()
+        };
+        let mut simulation = Simulation::new(params).unwrap();
+
+        // 2. Action
+        let mut events = Vec::new();
+        for _ in 0..200 {
+            simulation.tick().unwrap();
+            events.extend(simulation.drain_events());
+        }
+
+        // 3. Assertion
+        assert!(simulation.drain_events().is_empty());
+        assert!(
+            events
+                .iter()
+                .any(|event| matches!(event, SimulationEvent::FoodDiscovered { .. }))
+        );
+        assert!(
+            events
+                .iter()
+                .any(|event| matches!(event, SimulationEvent::FoodPickedUp { .. }))
+        );
+    }
+
+    #[test]
+    fn test_run_executes_requested_ticks() {
+        // 1. Setup
+        let params = SimulationOptions {
+            seed: Some(5),
+            ..SimulationOptions::default()
+        };
+        let mut batched = Simulation::new(params).unwrap();
+        let mut stepped = Simulation::new(params).unwrap();
+
+        // 2. Action
+        let summary = batched.run(100).unwrap();
+        for _ in 0..100 {
+            stepped.tick().unwrap();
+        }
+
+        // 3. Assertion
+        assert_eq!(summary.ticks_executed, 100);
+        assert_eq!(summary.stop_reason, StopCondition::TickLimit { ticks: 100 });
+        assert_eq!(summary.stats, stepped.get_world_statistics_dto().unwrap());
+        assert_eq!(
+            batched.get_world_state_dto().unwrap(),
+            stepped.get_world_state_dto().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_run_until_stops_when_colony_is_extinct() {
+        // 1. Setup
+        let params = SimulationOptions {
+            seed: Some(5),
+            starting_ants: 5,
+            ..SimulationOptions::default()
+        };
+        let mut config = SimulationConfig::default();
+        config.ant.min_health = 1;
+        config.ant.max_health = 3;
+        config.colony.queen_health = 3;
+        config.castes.soldier.spawn_weight = 0.0;
+        config.castes.scout.spawn_weight = 0.0;
+        let mut simulation = Simulation::with_config(params, config).unwrap();
+
+        // 2. Action
+        let summary = simulation
+            .run_until(&[
+                StopCondition::ColonyExtinct,
+                StopCondition::TickLimit { ticks: 1000 },
+            ])
+            .unwrap();
+
+        // 3. Assertion
+        assert_eq!(summary.stop_reason, StopCondition::ColonyExtinct);
+        assert_eq!(summary.stats.alive_ants, 0);
+        assert!(summary.ticks_executed < 1000);
+    }
+
+    #[test]
+    fn test_run_until_rejects_conditions_without_tick_limit() {
+        // 1. Setup
+        let mut simulation = Simulation::new(SimulationOptions::default()).unwrap();
+
+        // 2. Action
+        let unbounded = simulation.run_until(&[StopCondition::ColonyExtinct]);
+        let empty = simulation.run_until(&[]);
+
+        // 3. Assertion
+        assert_eq!(unbounded.unwrap_err(), SimulationError::UnboundedRun);
+        assert_eq!(empty.unwrap_err(), SimulationError::UnboundedRun);
+        assert_eq!(simulation.current_tick, 0);
+    }
+
+    #[test]
+    fn test_colony_starves_without_food() {
+        // 1. Setup
+        let params = SimulationOptions {
+            seed: Some(3),
+            starting_ants: 10,
+            starting_food_sources: 0,
+            ..SimulationOptions::default()
+        };
+        let mut config = SimulationConfig::default();
+        config.hunger.max_satiety = 10.0;
+        config.world.food_spawn_chance = 0.0;
+        let mut simulation = Simulation::with_config(params, config).unwrap();
+
+        // 2. Action
+        let summary = simulation.run(100).unwrap();
+
+        // 3. Assertion
+        assert_eq!(summary.stats.alive_ants, 0);
+        assert_eq!(summary.stats.starved_ants, 10);
+        assert!(
+            simulation
+                .drain_events()
+                .iter()
+                .any(|event| matches!(event, SimulationEvent::AntStarved { .. }))
+        );
+    }
+
     #[test]
     fn test_simulation_new_spawns_correct_entities() {
         // 1. Action
         let params = SimulationOptions::default();
-        let simulation = Simulation::new(params);
+        let simulation = Simulation::new(params).unwrap();
         let ants = simulation.world.query::<(&Position, &Ant)>().iter().count();
         let nests = simulation
             .world
@@ -242,7 +1043,7 @@ mod tests {
     fn test_get_world_state_dto_includes_all_entities_and_dimensions() {
         // 1. Setup
         let params = SimulationOptions::default();
-        let mut simulation = Simulation::new(params);
+        let mut simulation = Simulation::new(params).unwrap();
 
         // 2. Action
         let dto = simulation.get_world_state_dto().unwrap();
@@ -250,17 +1051,65 @@ mod tests {
         // 3. Assertion
         assert_eq!(dto.width, 100.0);
         assert_eq!(dto.height, 100.0);
-        assert_eq!(dto.nest, NestDto { x: 40.0, y: 40.0 });
+        assert_eq!(
+            dto.nest,
+            NestDto {
+                x: 40.0,
+                y: 40.0,
+                food_store: 0,
+                eggs: 0,
+                larvae: 0,
+                pupae: 0,
+                queen_health: Some(SimulationConfig::default().colony.queen_health),
+            }
+        );
         assert_eq!(dto.food_sources.len(), 50);
         assert_eq!(dto.ants.len(), 50);
     }
 
+    #[test]
+    fn test_get_world_state_dto_echoes_provided_seed() {
+        // 1. Setup
+        let params = SimulationOptions {
+            seed: Some(1234),
+            ..SimulationOptions::default()
+        };
+        let mut simulation = Simulation::new(params).unwrap();
+
+        // 2. Action
+        let dto = simulation.get_world_state_dto().unwrap();
+
+        // 3. Assertion
+        assert_eq!(dto.seed, 1234);
+    }
+
+    #[test]
+    fn test_simulation_with_same_seed_produces_identical_ticks() {
+        // 1. Setup
+        let params = SimulationOptions {
+            seed: Some(42),
+            ..SimulationOptions::default()
+        };
+        let mut simulation_a = Simulation::new(params).unwrap();
+        let mut simulation_b = Simulation::new(params).unwrap();
+
+        // 2. Action & Assertion
+        for _ in 0..500 {
+            simulation_a.tick().unwrap();
+            simulation_b.tick().unwrap();
+            assert_eq!(
+                simulation_a.get_world_state_dto().unwrap(),
+                simulation_b.get_world_state_dto().unwrap()
+            );
+        }
+    }
+
     #[test]
     fn test_get_world_statistics_includes_all_expected_stats() {
         // 1. Setup
         let params = SimulationOptions::default();
-        let mut simulation = Simulation::new(params);
-        simulation.tick();
+        let mut simulation = Simulation::new(params).unwrap();
+        simulation.tick().unwrap();
 
         // 2. Action
         let dto = simulation.get_world_statistics_dto().unwrap();
@@ -269,4 +1118,490 @@ mod tests {
         assert!(dto.alive_ants >= 50);
         assert!(dto.food_source_count >= 50);
     }
+
+    #[test]
+    fn test_disabled_system_does_not_run() {
+        // 1. Setup
+        let params = SimulationOptions {
+            starting_food_sources: 0,
+            ..SimulationOptions::default()
+        };
+        let mut config = SimulationConfig::default();
+        config.world.food_spawn_chance = 1.0;
+        let mut simulation = Simulation::with_config(params, config).unwrap();
+        simulation
+            .set_system_enabled("food_spawn_system", false)
+            .unwrap();
+
+        // 2. Action
+        simulation.run(10).unwrap();
+
+        // 3. Assertion
+        let dto = simulation.get_world_statistics_dto().unwrap();
+        assert_eq!(dto.food_source_count, 0);
+    }
+
+    #[test]
+    fn test_custom_system_runs_in_its_stage() {
+        // 1. Setup
+        let params = SimulationOptions::default();
+        let mut simulation = Simulation::new(params).unwrap();
+        simulation
+            .schedule_mut()
+            .add_system(Stage::Lifecycle, "starve_colony", |ctx| {
+                for (_, ant) in ctx.world.query_mut::<&mut Ant>() {
+                    ant.health = 0;
+                }
+                Ok(())
+            })
+            .unwrap();
+
+        // 2. Action
+        simulation.tick().unwrap();
+        let after_first_tick = simulation.get_world_state_dto().unwrap().ants;
+        simulation.tick().unwrap();
+        let after_second_tick = simulation.get_world_state_dto().unwrap().ants;
+
+        // 3. Assertion
+        // The custom system runs after ant_dying_system, so ants only start dying a tick later.
+        assert!(after_first_tick.iter().all(|ant| ant.health == 0));
+        assert!(
+            !after_first_tick
+                .iter()
+                .any(|ant| matches!(ant.state, AntStateDto::Dying(_)))
+        );
+        assert!(
+            after_second_tick
+                .iter()
+                .all(|ant| matches!(ant.state, AntStateDto::Dying(_)))
+        );
+    }
+
+    #[test]
+    fn test_load_keeps_disabled_systems() {
+        let mut simulation = Simulation::new(SimulationOptions::default()).unwrap();
+        simulation
+            .set_system_enabled("pheromone_decay_system", false)
+            .unwrap();
+
+        let restored = Simulation::load(&simulation.save().unwrap()).unwrap();
+
+        assert_eq!(
+            restored.schedule().is_enabled("pheromone_decay_system"),
+            Some(false)
+        );
+    }
+
+    #[cfg(feature = "profiling")]
+    #[test]
+    fn test_profile_times_every_scheduled_system() {
+        // 1. Setup
+        let mut simulation = Simulation::new(SimulationOptions::default()).unwrap();
+
+        // 2. Action
+        simulation.run(5).unwrap();
+        let profile = simulation.profile();
+
+        // 3. Assertion
+        let names: Vec<&str> = profile.systems.iter().map(|s| s.name.as_str()).collect();
+        let scheduled: Vec<&str> = simulation.schedule().system_names().collect();
+        assert_eq!(profile.ticks, 5);
+        assert_eq!(names, scheduled);
+        let systems_mean: f64 = profile.systems.iter().map(|s| s.mean_micros).sum();
+        assert!(profile.tick_mean_micros >= systems_mean);
+    }
+
+    #[cfg(feature = "profiling")]
+    #[test]
+    fn test_profile_counts_entities_and_forgets_removed_systems() {
+        // 1. Setup
+        let mut simulation = Simulation::new(SimulationOptions::default()).unwrap();
+        simulation.run(1).unwrap();
+
+        // 2. Action
+        let _removed = simulation
+            .schedule_mut()
+            .remove_system("food_spawn_system")
+            .unwrap();
+        simulation.run(1).unwrap();
+        let profile = simulation.profile();
+
+        // 3. Assertion
+        let ants = simulation.world.query::<&Ant>().iter().count() as u32;
+        let lifecycle = profile
+            .systems
+            .iter()
+            .find(|system| system.name == "ant_lifecycle_system")
+            .unwrap();
+        assert_eq!(lifecycle.entities, ants);
+        assert!(
+            profile
+                .systems
+                .iter()
+                .all(|system| system.name != "food_spawn_system")
+        );
+    }
+
+    #[test]
+    fn test_tick_with_dt_does_not_depend_on_frame_length() {
+        // 1. Setup
+        let params = SimulationOptions {
+            seed: Some(11),
+            ..SimulationOptions::default()
+        };
+        let mut fast_forwarded = Simulation::new(params).unwrap();
+        let mut frame_by_frame = Simulation::new(params).unwrap();
+
+        // 2. Action
+        let summary = fast_forwarded.tick_with_dt(20.0).unwrap();
+        for _ in 0..(20 * 64) {
+            frame_by_frame.tick_with_dt(1.0 / 64.0).unwrap();
+        }
+
+        // 3. Assertion
+        assert_eq!(summary.ticks_run, fast_forwarded.current_tick());
+        assert_eq!(summary.dropped_seconds, 0.0);
+        assert_eq!(fast_forwarded.current_tick(), frame_by_frame.current_tick());
+        assert_eq!(
+            fast_forwarded.get_world_state_dto().unwrap(),
+            frame_by_frame.get_world_state_dto().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_tick_with_dt_drops_time_beyond_tick_cap() {
+        // 1. Setup
+        let mut config = SimulationConfig::default();
+        config.world.seconds_per_tick = 0.5;
+        config.world.max_ticks_per_step = 10;
+        let mut simulation = Simulation::with_config(SimulationOptions::default(), config).unwrap();
+
+        // 2. Action
+        let capped = simulation.tick_with_dt(10.25).unwrap();
+        let next = simulation.tick_with_dt(0.25).unwrap();
+
+        // 3. Assertion
+        assert_eq!(capped.ticks_run, 10);
+        assert_eq!(capped.dropped_seconds, 5.0);
+        assert_eq!(next.ticks_run, 1);
+        assert_eq!(next.dropped_seconds, 0.0);
+        assert_eq!(simulation.current_tick(), 11);
+    }
+
+    #[test]
+    fn test_tick_with_dt_runs_every_tick_of_the_default_length() {
+        // 1. Setup
+        let mut tenths = Simulation::new(SimulationOptions::default()).unwrap();
+        let mut seconds = Simulation::new(SimulationOptions::default()).unwrap();
+
+        // 2. Action
+        for _ in 0..100 {
+            tenths.tick_with_dt(0.1).unwrap();
+        }
+        for _ in 0..10 {
+            seconds.tick_with_dt(1.0).unwrap();
+        }
+
+        // 3. Assertion
+        assert_eq!(tenths.current_tick(), 100);
+        assert_eq!(seconds.current_tick(), 100);
+    }
+
+    #[test]
+    fn test_tick_with_dt_rejects_negative_time() {
+        let mut simulation = Simulation::new(SimulationOptions::default()).unwrap();
+
+        let result = simulation.tick_with_dt(-1.0);
+
+        assert_eq!(result, Err(SimulationError::InvalidTimeStep(-1.0)));
+    }
+
+    fn record_session() -> CommandLog {
+        let params = SimulationOptions {
+            seed: Some(21),
+            ..SimulationOptions::default()
+        };
+        let mut simulation = Simulation::new(params).unwrap();
+        simulation.start_recording(10).unwrap();
+
+        simulation.run(30).unwrap();
+        simulation
+            .patch_config(serde_json::json!({ "movement": { "wanderProbability": 0.5 } }))
+            .unwrap();
+        simulation.run(25).unwrap();
+        simulation
+            .set_system_enabled("food_spawn_system", false)
+            .unwrap();
+        simulation.run(25).unwrap();
+
+        simulation.stop_recording().unwrap()
+    }
+
+    #[test]
+    fn test_replay_reproduces_tick_with_dt_session() {
+        // 1. Setup
+        let params = SimulationOptions {
+            seed: Some(13),
+            ..SimulationOptions::default()
+        };
+        let mut simulation = Simulation::new(params).unwrap();
+        simulation.start_recording(1).unwrap();
+        for _ in 0..20 {
+            simulation.tick_with_dt(0.25).unwrap();
+        }
+        let log = simulation.stop_recording().unwrap();
+
+        // 2. Action
+        let report = log.replay().unwrap();
+
+        // 3. Assertion
+        assert_eq!(log.ticks, 50);
+        assert_eq!(report.divergence, None);
+        assert_eq!(report.ticks_replayed, 50);
+        assert_eq!(
+            report.simulation.state_hash().unwrap(),
+            simulation.state_hash().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_replay_reproduces_recorded_session() {
+        // 1. Setup
+        let log = CommandLog::from_json(&record_session().to_json().unwrap()).unwrap();
+
+        // 2. Action
+        let report = log.replay().unwrap();
+
+        // 3. Assertion
+        assert_eq!(log.ticks, 80);
+        assert_eq!(log.commands.len(), 2);
+        assert_eq!(log.checkpoints.len(), 9);
+        assert_eq!(report.ticks_replayed, 80);
+        assert_eq!(report.divergence, None);
+        assert_eq!(
+            report.simulation.state_hash().unwrap(),
+            log.checkpoints.last().unwrap().hash
+        );
+    }
+
+    #[test]
+    fn test_replay_stops_at_first_diverging_checkpoint() {
+        // 1. Setup
+        let mut log = record_session();
+        log.commands.remove(0);
+
+        // 2. Action
+        let report = log.replay().unwrap();
+
+        // 3. Assertion
+        let divergence = report.divergence.unwrap();
+        assert_eq!(divergence.tick, 40);
+        assert_eq!(report.ticks_replayed, 40);
+        assert_eq!(report.simulation.current_tick(), 40);
+    }
+
+    #[test]
+    fn test_state_hash_matches_for_identical_runs() {
+        // 1. Setup
+        let params = SimulationOptions {
+            seed: Some(8),
+            ..SimulationOptions::default()
+        };
+        let mut first = Simulation::new(params).unwrap();
+        let mut second = Simulation::new(params).unwrap();
+
+        // 2. Action
+        first.run(50).unwrap();
+        second.run(50).unwrap();
+        let hash_before_change = second.state_hash().unwrap();
+        if let Some((_, ant)) = second.world.query_mut::<&mut Ant>().into_iter().next() {
+            ant.health += 1;
+        }
+
+        // 3. Assertion
+        assert_eq!(first.state_hash().unwrap(), hash_before_change);
+        assert_ne!(first.state_hash().unwrap(), second.state_hash().unwrap());
+    }
+
+    #[test]
+    fn test_state_hash_covers_rng_state() {
+        let mut simulation = Simulation::new(SimulationOptions::default()).unwrap();
+        let hash = simulation.state_hash().unwrap();
+
+        simulation.rng.random::<u32>();
+
+        assert_ne!(simulation.state_hash().unwrap(), hash);
+    }
+
+    #[test]
+    fn test_state_hash_covers_config_and_disabled_systems() {
+        // 1. Setup
+        let simulation = Simulation::new(SimulationOptions::default()).unwrap();
+        let mut patched = Simulation::new(SimulationOptions::default()).unwrap();
+        let mut disabled = Simulation::new(SimulationOptions::default()).unwrap();
+        let mut carried = Simulation::new(SimulationOptions::default()).unwrap();
+        let hash = simulation.state_hash().unwrap();
+
+        // 2. Action
+        patched
+            .patch_config(serde_json::json!({ "movement": { "wanderProbability": 0.5 } }))
+            .unwrap();
+        disabled
+            .set_system_enabled("food_spawn_system", false)
+            .unwrap();
+        carried.tick_with_dt(0.05).unwrap();
+
+        // 3. Assertion
+        assert_eq!(hash, simulation.state_hash().unwrap());
+        assert_ne!(patched.state_hash().unwrap(), hash);
+        assert_ne!(disabled.state_hash().unwrap(), hash);
+        assert_ne!(carried.state_hash().unwrap(), hash);
+    }
+
+    fn without_ids(simulation: &mut Simulation) -> WorldDto {
+        let mut dto = simulation.get_world_state_dto().unwrap();
+        dto.ants.iter_mut().for_each(|ant| ant.id = 0);
+        dto.food_sources.iter_mut().for_each(|food| food.id = 0);
+        dto
+    }
+
+    #[test]
+    fn test_seek_returns_to_an_earlier_tick() {
+        // 1. Setup
+        let params = SimulationOptions {
+            seed: Some(13),
+            ..SimulationOptions::default()
+        };
+        let mut rewound = Simulation::new(params).unwrap();
+        let mut reference = Simulation::new(params).unwrap();
+        rewound.enable_rewind(10, 8);
+        rewound.run(25).unwrap();
+        rewound
+            .patch_config(serde_json::json!({ "movement": { "speed": 12.0 } }))
+            .unwrap();
+        rewound.run(32).unwrap();
+        reference.run(25).unwrap();
+        reference
+            .patch_config(serde_json::json!({ "movement": { "speed": 12.0 } }))
+            .unwrap();
+        reference.run(8).unwrap();
+
+        // 2. Action
+        rewound.seek(33).unwrap();
+
+        // 3. Assertion
+        assert_eq!(rewound.current_tick(), 33);
+        assert_eq!(without_ids(&mut rewound), without_ids(&mut reference));
+        assert_eq!(
+            rewound.get_world_statistics_dto().unwrap(),
+            reference.get_world_statistics_dto().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_step_back_then_forward_continues_the_same_run() {
+        // 1. Setup
+        let mut simulation = Simulation::new(SimulationOptions::default()).unwrap();
+        simulation.enable_rewind(5, 4);
+        simulation.run(12).unwrap();
+        let expected = without_ids(&mut simulation);
+
+        // 2. Action
+        simulation.step_back().unwrap();
+        simulation.step_back().unwrap();
+        let rewound_tick = simulation.current_tick();
+        simulation.run(2).unwrap();
+
+        // 3. Assertion
+        assert_eq!(rewound_tick, 10);
+        assert_eq!(without_ids(&mut simulation), expected);
+    }
+
+    #[test]
+    fn test_seek_outside_window_fails() {
+        // 1. Setup
+        let mut without_rewind = Simulation::new(SimulationOptions::default()).unwrap();
+        let mut with_rewind = Simulation::new(SimulationOptions::default()).unwrap();
+        without_rewind.run(5).unwrap();
+        with_rewind.enable_rewind(5, 2);
+        with_rewind.run(20).unwrap();
+
+        // 2. Action
+        let disabled = without_rewind.seek(1);
+        let too_early = with_rewind.seek(12);
+
+        // 3. Assertion
+        assert_eq!(disabled, Err(SimulationError::RewindDisabled));
+        assert_eq!(
+            too_early,
+            Err(SimulationError::TickOutsideRewindWindow {
+                tick: 12,
+                earliest: 15
+            })
+        );
+        assert_eq!(with_rewind.current_tick(), 20);
+    }
+
+    #[test]
+    fn test_config_changes_keep_the_rewind_window() {
+        // 1. Setup
+        let params = SimulationOptions {
+            seed: Some(17),
+            ..SimulationOptions::default()
+        };
+        let mut rewound = Simulation::new(params).unwrap();
+        let mut straight = Simulation::new(params).unwrap();
+        rewound.enable_rewind(10, 5);
+        rewound.run(50).unwrap();
+        straight.run(50).unwrap();
+        let mut expected = None;
+        for (round, wander_probability) in [0.2, 0.3, 0.4, 0.6, 0.7].into_iter().enumerate() {
+            let patch =
+                serde_json::json!({ "movement": { "wanderProbability": wander_probability } });
+            rewound.patch_config(patch.clone()).unwrap();
+            straight.patch_config(patch).unwrap();
+            if round == 3 {
+                expected = Some((without_ids(&mut straight), straight.config().clone()));
+            }
+            rewound.tick().unwrap();
+            straight.tick().unwrap();
+        }
+
+        // 2. Action
+        rewound.seek(53).unwrap();
+        let after_changes = (without_ids(&mut rewound), rewound.config().clone());
+        let before_changes = rewound.seek(45);
+
+        // 3. Assertion
+        assert_eq!(Some(after_changes), expected);
+        assert_eq!(before_changes, Ok(()));
+        assert_eq!(rewound.current_tick(), 45);
+    }
+
+    #[test]
+    fn test_start_recording_after_first_tick_fails() {
+        let mut simulation = Simulation::new(SimulationOptions::default()).unwrap();
+        simulation.tick().unwrap();
+
+        let result = simulation.start_recording(10);
+
+        assert_eq!(result, Err(SimulationError::RecordingStartedLate(1)));
+    }
+
+    #[test]
+    fn test_tick_advances_clock_and_ant_ages() {
+        // 1. Setup
+        let params = SimulationOptions::default();
+        let mut simulation = Simulation::new(params).unwrap();
+
+        // 2. Action
+        simulation.run(25).unwrap();
+
+        // 3. Assertion
+        assert_eq!(simulation.current_tick(), 25);
+        assert_eq!(simulation.get_world_statistics_dto().unwrap().tick, 25);
+        let ants = simulation.get_world_state_dto().unwrap().ants;
+        assert!(ants.iter().any(|ant| ant.age == 25));
+        assert!(ants.iter().all(|ant| ant.age <= 25));
+    }
 }
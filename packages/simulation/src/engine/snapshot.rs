@@ -0,0 +1,572 @@
+use crate::components::world::{
+    Ant, AntState, Brood, CarryCapacity, Caste, FoodPayload, FoodSource, HomingVector, Nest,
+    PheromoneDeposit, PheromoneToFood, PheromoneToNest, Position, Queen, Role, RouteMemory,
+    Satiety, Stamina, TandemFollower, TandemLeader, Target, Velocity,
+};
+use crate::engine::config::{ConfigError, SimulationConfig};
+use crate::engine::simulation::SimulationOptions;
+use crate::engine::stats::Stats;
+use hecs::{Archetype, Entity, EntityBuilder, EntityRef, World};
+use rand_pcg::Pcg64;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// The snapshot format version written by `Simulation::save`.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ComponentKind {
+    Position,
+    Velocity,
+    Target,
+    Ant,
+    AntState,
+    FoodPayload,
+    PheromoneDeposit,
+    PheromoneToFood,
+    PheromoneToNest,
+    Nest,
+    FoodSource,
+    Caste,
+    Queen,
+    Brood,
+    Satiety,
+    CarryCapacity,
+    RouteMemory,
+    HomingVector,
+    TandemLeader,
+    TandemFollower,
+    Role,
+    Stamina,
+}
+
+impl ComponentKind {
+    const ALL: [ComponentKind; 22] = [
+        ComponentKind::Position,
+        ComponentKind::Velocity,
+        ComponentKind::Target,
+        ComponentKind::Ant,
+        ComponentKind::AntState,
+        ComponentKind::FoodPayload,
+        ComponentKind::PheromoneDeposit,
+        ComponentKind::PheromoneToFood,
+        ComponentKind::PheromoneToNest,
+        ComponentKind::Nest,
+        ComponentKind::FoodSource,
+        ComponentKind::Caste,
+        ComponentKind::Queen,
+        ComponentKind::Brood,
+        ComponentKind::Satiety,
+        ComponentKind::CarryCapacity,
+        ComponentKind::RouteMemory,
+        ComponentKind::HomingVector,
+        ComponentKind::TandemLeader,
+        ComponentKind::TandemFollower,
+        ComponentKind::Role,
+        ComponentKind::Stamina,
+    ];
+
+    fn is_in(self, archetype: &Archetype) -> bool {
+        match self {
+            ComponentKind::Position => archetype.has::<Position>(),
+            ComponentKind::Velocity => archetype.has::<Velocity>(),
+            ComponentKind::Target => archetype.has::<Target>(),
+            ComponentKind::Ant => archetype.has::<Ant>(),
+            ComponentKind::AntState => archetype.has::<AntState>(),
+            ComponentKind::FoodPayload => archetype.has::<FoodPayload>(),
+            ComponentKind::PheromoneDeposit => archetype.has::<PheromoneDeposit>(),
+            ComponentKind::PheromoneToFood => archetype.has::<PheromoneToFood>(),
+            ComponentKind::PheromoneToNest => archetype.has::<PheromoneToNest>(),
+            ComponentKind::Nest => archetype.has::<Nest>(),
+            ComponentKind::FoodSource => archetype.has::<FoodSource>(),
+            ComponentKind::Caste => archetype.has::<Caste>(),
+            ComponentKind::Queen => archetype.has::<Queen>(),
+            ComponentKind::Brood => archetype.has::<Brood>(),
+            ComponentKind::Satiety => archetype.has::<Satiety>(),
+            ComponentKind::CarryCapacity => archetype.has::<CarryCapacity>(),
+            ComponentKind::RouteMemory => archetype.has::<RouteMemory>(),
+            ComponentKind::HomingVector => archetype.has::<HomingVector>(),
+            ComponentKind::TandemLeader => archetype.has::<TandemLeader>(),
+            ComponentKind::TandemFollower => archetype.has::<TandemFollower>(),
+            ComponentKind::Role => archetype.has::<Role>(),
+            ComponentKind::Stamina => archetype.has::<Stamina>(),
+        }
+    }
+}
+
+/// The components of a single entity. `id` is the entity handle at save time and
+/// `target` refers to another entity by that same handle.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct EntitySnapshot {
+    pub id: u64,
+    pub position: Option<Position>,
+    pub velocity: Option<Velocity>,
+    pub target: Option<u64>,
+    pub ant: Option<Ant>,
+    pub ant_state: Option<AntState>,
+    pub food_payload: Option<FoodPayload>,
+    pub pheromone_deposit: Option<PheromoneDeposit>,
+    pub pheromone_to_food: Option<PheromoneToFood>,
+    pub pheromone_to_nest: Option<PheromoneToNest>,
+    pub nest: Option<Nest>,
+    pub food_source: Option<FoodSource>,
+    pub caste: Option<Caste>,
+    pub queen: Option<Queen>,
+    pub brood: Option<Brood>,
+    pub satiety: Option<Satiety>,
+    pub carry_capacity: Option<CarryCapacity>,
+    pub route_memory: Option<RouteMemory>,
+    pub homing_vector: Option<HomingVector>,
+    pub tandem_leader: Option<TandemLeader>,
+    pub tandem_follower: Option<TandemFollower>,
+    pub role: Option<Role>,
+    pub stamina: Option<Stamina>,
+}
+
+impl EntitySnapshot {
+    fn capture(entity: EntityRef) -> Self {
+        Self {
+            id: entity.entity().to_bits().get(),
+            position: entity.get::<&Position>().map(|c| *c),
+            velocity: entity.get::<&Velocity>().map(|c| (*c).clone()),
+            target: entity.get::<&Target>().map(|c| c.0.to_bits().get()),
+            ant: entity.get::<&Ant>().map(|c| (*c).clone()),
+            ant_state: entity.get::<&AntState>().map(|c| *c),
+            food_payload: entity.get::<&FoodPayload>().map(|c| *c),
+            pheromone_deposit: entity.get::<&PheromoneDeposit>().map(|c| (*c).clone()),
+            pheromone_to_food: entity.get::<&PheromoneToFood>().map(|c| (*c).clone()),
+            pheromone_to_nest: entity.get::<&PheromoneToNest>().map(|c| (*c).clone()),
+            nest: entity.get::<&Nest>().map(|c| (*c).clone()),
+            food_source: entity.get::<&FoodSource>().map(|c| (*c).clone()),
+            caste: entity.get::<&Caste>().map(|c| *c),
+            queen: entity.get::<&Queen>().map(|c| (*c).clone()),
+            brood: entity.get::<&Brood>().map(|c| (*c).clone()),
+            satiety: entity.get::<&Satiety>().map(|c| *c),
+            carry_capacity: entity.get::<&CarryCapacity>().map(|c| *c),
+            route_memory: entity.get::<&RouteMemory>().map(|c| *c),
+            homing_vector: entity.get::<&HomingVector>().map(|c| *c),
+            tandem_leader: entity.get::<&TandemLeader>().map(|c| (*c).clone()),
+            tandem_follower: entity.get::<&TandemFollower>().map(|c| (*c).clone()),
+            role: entity.get::<&Role>().map(|c| *c),
+            stamina: entity.get::<&Stamina>().map(|c| *c),
+        }
+    }
+
+    /// Stand-in values used to recreate archetypes that were empty at save time.
+    fn placeholder() -> Self {
+        Self {
+            id: 0,
+            position: Some(Position { x: 0.0, y: 0.0 }),
+            velocity: Some(Velocity { dx: 0.0, dy: 0.0 }),
+            target: Some(0),
+            ant: Some(Ant {
+                health: 0,
+                birth_tick: 0,
+            }),
+            ant_state: Some(AntState::Wandering),
+            food_payload: Some(FoodPayload(0)),
+            pheromone_deposit: Some(PheromoneDeposit { strength: 0.0 }),
+            pheromone_to_food: Some(PheromoneToFood),
+            pheromone_to_nest: Some(PheromoneToNest),
+            nest: Some(Nest::new()),
+            food_source: Some(FoodSource {
+                amount: 0,
+                birth_tick: 0,
+            }),
+            caste: Some(Caste::Worker),
+            queen: Some(Queen { health: 0 }),
+            brood: Some(Brood::egg()),
+            satiety: Some(Satiety(0.0)),
+            carry_capacity: Some(CarryCapacity(0)),
+            route_memory: Some(RouteMemory {
+                position: Position { x: 0.0, y: 0.0 },
+                failed_visits: 0,
+            }),
+            homing_vector: Some(HomingVector {
+                dx: 0.0,
+                dy: 0.0,
+                last_position: Position { x: 0.0, y: 0.0 },
+            }),
+            tandem_leader: Some(TandemLeader),
+            tandem_follower: Some(TandemFollower),
+            role: Some(Role::Forager),
+            stamina: Some(Stamina(0.0)),
+        }
+    }
+
+    fn add_to(
+        &self,
+        builder: &mut EntityBuilder,
+        kind: ComponentKind,
+    ) -> Result<(), SnapshotError> {
+        let missing = || SnapshotError::MissingComponent {
+            entity: self.id,
+            component: kind,
+        };
+
+        match kind {
+            ComponentKind::Position => builder.add(self.position.ok_or_else(missing)?),
+            ComponentKind::Velocity => builder.add(self.velocity.clone().ok_or_else(missing)?),
+            // Targets are pointed at their remapped entity once every entity exists.
+            ComponentKind::Target => builder.add(Target(
+                self.target.map(|_| Entity::DANGLING).ok_or_else(missing)?,
+            )),
+            ComponentKind::Ant => builder.add(self.ant.clone().ok_or_else(missing)?),
+            ComponentKind::AntState => builder.add(self.ant_state.ok_or_else(missing)?),
+            ComponentKind::FoodPayload => builder.add(self.food_payload.ok_or_else(missing)?),
+            ComponentKind::PheromoneDeposit => {
+                builder.add(self.pheromone_deposit.clone().ok_or_else(missing)?)
+            }
+            // Unit markers are saved as `null`, so the archetype alone says they are present.
+            ComponentKind::PheromoneToFood => builder.add(PheromoneToFood),
+            ComponentKind::PheromoneToNest => builder.add(PheromoneToNest),
+            ComponentKind::Nest => builder.add(self.nest.clone().ok_or_else(missing)?),
+            ComponentKind::FoodSource => builder.add(self.food_source.clone().ok_or_else(missing)?),
+            ComponentKind::Caste => builder.add(self.caste.ok_or_else(missing)?),
+            ComponentKind::Queen => builder.add(self.queen.clone().ok_or_else(missing)?),
+            ComponentKind::Brood => builder.add(self.brood.clone().ok_or_else(missing)?),
+            ComponentKind::Satiety => builder.add(self.satiety.ok_or_else(missing)?),
+            ComponentKind::CarryCapacity => builder.add(self.carry_capacity.ok_or_else(missing)?),
+            ComponentKind::RouteMemory => builder.add(self.route_memory.ok_or_else(missing)?),
+            ComponentKind::HomingVector => builder.add(self.homing_vector.ok_or_else(missing)?),
+            ComponentKind::TandemLeader => builder.add(TandemLeader),
+            ComponentKind::TandemFollower => builder.add(TandemFollower),
+            ComponentKind::Role => builder.add(self.role.ok_or_else(missing)?),
+            ComponentKind::Stamina => builder.add(self.stamina.ok_or_else(missing)?),
+        };
+        Ok(())
+    }
+}
+
+/// The entities of one hecs archetype, in storage order.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchetypeSnapshot {
+    pub components: Vec<ComponentKind>,
+    pub entities: Vec<EntitySnapshot>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationSnapshot {
+    pub version: u32,
+    pub options: SimulationOptions,
+    pub config: SimulationConfig,
+    pub seed: u32,
+    #[serde(default)]
+    pub current_tick: u32,
+    /// Time passed to `tick_with_dt` that has not yet added up to a whole tick, in
+    /// microseconds.
+    #[serde(default)]
+    pub pending_micros: u64,
+    pub rng: Pcg64,
+    pub stats: Stats,
+    /// Built-in systems that were turned off. Custom systems must be registered again after loading.
+    #[serde(default)]
+    pub disabled_systems: Vec<String>,
+    pub archetypes: Vec<ArchetypeSnapshot>,
+}
+
+impl SimulationSnapshot {
+    pub fn to_json(&self) -> Result<String, SnapshotError> {
+        serde_json::to_string(self).map_err(|e| SnapshotError::Serialize(e.to_string()))
+    }
+
+    pub fn from_json(input: &str) -> Result<Self, SnapshotError> {
+        #[derive(Deserialize)]
+        struct Header {
+            version: u32,
+        }
+
+        let header: Header =
+            serde_json::from_str(input).map_err(|e| SnapshotError::Deserialize(e.to_string()))?;
+        if header.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(header.version));
+        }
+
+        serde_json::from_str(input).map_err(|e| SnapshotError::Deserialize(e.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotError {
+    Serialize(String),
+    Deserialize(String),
+    UnsupportedVersion(u32),
+    InvalidConfig(ConfigError),
+    DuplicateEntity(u64),
+    MissingComponent {
+        entity: u64,
+        component: ComponentKind,
+    },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Serialize(e) => write!(f, "Failed to serialize snapshot: {e}"),
+            SnapshotError::Deserialize(e) => write!(f, "Failed to deserialize snapshot: {e}"),
+            SnapshotError::UnsupportedVersion(version) => write!(
+                f,
+                "Unsupported snapshot version {version}, expected {SNAPSHOT_VERSION}"
+            ),
+            SnapshotError::InvalidConfig(e) => write!(f, "Snapshot has an invalid config: {e}"),
+            SnapshotError::DuplicateEntity(id) => {
+                write!(f, "Snapshot contains entity {id} more than once")
+            }
+            SnapshotError::MissingComponent { entity, component } => write!(
+                f,
+                "Snapshot entity {entity} is missing its {component:?} component"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// Capture every archetype in storage order, including empty ones, so that a restored
+/// world iterates its entities in exactly the same order as the original.
+///
+/// Components that are not listed in [`ComponentKind`] are not captured.
+pub fn capture_world(world: &World) -> Vec<ArchetypeSnapshot> {
+    let entities: HashMap<u32, EntityRef> = world
+        .iter()
+        .map(|entity| (entity.entity().id(), entity))
+        .collect();
+
+    world
+        .archetypes()
+        .map(|archetype| ArchetypeSnapshot {
+            components: ComponentKind::ALL
+                .into_iter()
+                .filter(|kind| kind.is_in(archetype))
+                .collect(),
+            entities: archetype
+                .ids()
+                .iter()
+                .map(|id| EntitySnapshot::capture(entities[id]))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Capture every entity in the world, ordered by entity handle rather than by archetype.
+pub fn capture_entities(world: &World) -> Vec<EntitySnapshot> {
+    let mut entities: Vec<EntitySnapshot> = world.iter().map(EntitySnapshot::capture).collect();
+    entities.sort_by_key(|entity| entity.id);
+    entities
+}
+
+/// Rebuild a world from captured archetypes. Entities get new handles, and every
+/// `Target` is remapped to the new handle of the entity it pointed at. Targets that
+/// were already dangling at save time stay dangling.
+pub fn restore_world(archetypes: &[ArchetypeSnapshot]) -> Result<World, SnapshotError> {
+    let mut world = World::new();
+    let mut builder = EntityBuilder::new();
+    let mut remapped: HashMap<u64, Entity> = HashMap::new();
+    let mut targets = Vec::new();
+    let placeholder = EntitySnapshot::placeholder();
+
+    for archetype in archetypes {
+        if archetype.entities.is_empty() {
+            // Spawning and despawning a stand-in creates the archetype in its original slot.
+            for kind in &archetype.components {
+                placeholder.add_to(&mut builder, *kind)?;
+            }
+            let entity = world.spawn(builder.build());
+            world.despawn(entity).ok();
+            continue;
+        }
+
+        for snapshot in &archetype.entities {
+            for kind in &archetype.components {
+                snapshot.add_to(&mut builder, *kind)?;
+            }
+            let entity = world.spawn(builder.build());
+
+            if remapped.insert(snapshot.id, entity).is_some() {
+                return Err(SnapshotError::DuplicateEntity(snapshot.id));
+            }
+            if archetype.components.contains(&ComponentKind::Target)
+                && let Some(target) = snapshot.target
+            {
+                targets.push((entity, target));
+            }
+        }
+    }
+
+    for (entity, target) in targets {
+        if let Ok(mut component) = world.get::<&mut Target>(entity) {
+            component.0 = remapped.get(&target).copied().unwrap_or(Entity::DANGLING);
+        }
+    }
+
+    Ok(world)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_world_remaps_targets_to_new_entities() {
+        // 1. Setup
+        let mut world = World::new();
+        // Free up a few handles so the restored world allocates different ones.
+        for _ in 0..3 {
+            let entity = world.spawn((Position { x: 0.0, y: 0.0 },));
+            world.despawn(entity).unwrap();
+        }
+        let food_entity = world.spawn((
+            Position { x: 10.0, y: 10.0 },
+            FoodSource {
+                amount: 50,
+                birth_tick: 0,
+            },
+        ));
+        world.spawn((
+            Position { x: 9.0, y: 9.0 },
+            AntState::Foraging,
+            Target(food_entity),
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+        ));
+
+        // 2. Action
+        let restored = restore_world(&capture_world(&world)).unwrap();
+
+        // 3. Assertion
+        let mut query = restored.query::<(&Target, &Ant)>();
+        let (_, (target, _)) = query.iter().next().unwrap();
+        let food_source = restored.get::<&FoodSource>(target.0).unwrap();
+        assert_eq!(food_source.amount, 50);
+    }
+
+    #[test]
+    fn test_restore_world_keeps_dangling_targets_dangling() {
+        // 1. Setup
+        let mut world = World::new();
+        let food_entity = world.spawn((
+            Position { x: 10.0, y: 10.0 },
+            FoodSource {
+                amount: 0,
+                birth_tick: 0,
+            },
+        ));
+        let ant_entity = world.spawn((Position { x: 9.0, y: 9.0 }, Target(food_entity)));
+        world.despawn(food_entity).unwrap();
+
+        // 2. Action
+        let restored = restore_world(&capture_world(&world)).unwrap();
+
+        // 3. Assertion
+        assert_eq!(restored.len(), 1);
+        let mut query = restored.query::<&Target>();
+        let (_, target) = query.iter().next().unwrap();
+        assert!(!restored.contains(target.0));
+        assert!(world.get::<&Target>(ant_entity).is_ok());
+    }
+
+    #[test]
+    fn test_restore_world_preserves_archetype_order() {
+        // 1. Setup
+        let mut world = World::new();
+        let emptied = world.spawn((Position { x: 0.0, y: 0.0 }, FoodPayload(1)));
+        world.spawn((Position { x: 1.0, y: 1.0 }, Nest::new()));
+        world.spawn((
+            Position { x: 2.0, y: 2.0 },
+            FoodSource {
+                amount: 1,
+                birth_tick: 0,
+            },
+        ));
+        world.remove_one::<FoodPayload>(emptied).unwrap();
+
+        // 2. Action
+        let mut restored = restore_world(&capture_world(&world)).unwrap();
+        restored.spawn((Position { x: 3.0, y: 3.0 }, FoodPayload(1)));
+        world.spawn((Position { x: 3.0, y: 3.0 }, FoodPayload(1)));
+
+        // 3. Assertion
+        let positions = |world: &World| -> Vec<Position> {
+            world
+                .query::<&Position>()
+                .iter()
+                .map(|(_, pos)| *pos)
+                .collect()
+        };
+        assert_eq!(positions(&restored), positions(&world));
+    }
+
+    #[test]
+    fn test_restore_world_keeps_marker_components_through_json() {
+        // 1. Setup
+        let mut world = World::new();
+        world.spawn((
+            Position { x: 1.0, y: 1.0 },
+            PheromoneDeposit { strength: 5.0 },
+            PheromoneToFood,
+        ));
+        world.spawn((
+            Position { x: 2.0, y: 2.0 },
+            PheromoneDeposit { strength: 3.0 },
+            PheromoneToNest,
+        ));
+        let json = serde_json::to_string(&capture_world(&world)).unwrap();
+
+        // 2. Action
+        let archetypes: Vec<ArchetypeSnapshot> = serde_json::from_str(&json).unwrap();
+        let restored = restore_world(&archetypes).unwrap();
+
+        // 3. Assertion
+        let to_food: Vec<f32> = restored
+            .query::<&PheromoneDeposit>()
+            .with::<&PheromoneToFood>()
+            .iter()
+            .map(|(_, deposit)| deposit.strength)
+            .collect();
+        let to_nest: Vec<f32> = restored
+            .query::<&PheromoneDeposit>()
+            .with::<&PheromoneToNest>()
+            .iter()
+            .map(|(_, deposit)| deposit.strength)
+            .collect();
+        assert_eq!(to_food, vec![5.0]);
+        assert_eq!(to_nest, vec![3.0]);
+    }
+
+    #[test]
+    fn test_from_json_rejects_unsupported_version() {
+        let error = SimulationSnapshot::from_json(r#"{ "version": 999 }"#).unwrap_err();
+        assert_eq!(error, SnapshotError::UnsupportedVersion(999));
+    }
+
+    #[test]
+    fn test_restore_world_rejects_missing_component() {
+        let archetypes = vec![ArchetypeSnapshot {
+            components: vec![ComponentKind::Position, ComponentKind::Nest],
+            entities: vec![EntitySnapshot {
+                id: 1,
+                position: Some(Position { x: 0.0, y: 0.0 }),
+                ..EntitySnapshot::default()
+            }],
+        }];
+
+        let Err(error) = restore_world(&archetypes) else {
+            panic!("Expected restoring an incomplete entity to fail");
+        };
+
+        assert_eq!(
+            error,
+            SnapshotError::MissingComponent {
+                entity: 1,
+                component: ComponentKind::Nest
+            }
+        );
+    }
+}
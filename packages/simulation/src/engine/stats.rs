@@ -1,9 +1,42 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Clone, Copy, Serialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Stats {
     pub alive_ants: u32,
     pub dead_ants: u32,
     pub food_sources: u32,
     pub food_in_nest: u32,
+    #[serde(default)]
+    pub eggs: u32,
+    #[serde(default)]
+    pub larvae: u32,
+    #[serde(default)]
+    pub pupae: u32,
+    #[serde(default)]
+    pub queen_alive: bool,
+    #[serde(default)]
+    pub starved_ants: u32,
+    #[serde(default)]
+    pub meals_eaten: u32,
+    #[serde(default)]
+    pub tandem_runs_started: u32,
+    #[serde(default)]
+    pub tandem_runs_completed: u32,
+    #[serde(default)]
+    pub nurses: u32,
+    #[serde(default)]
+    pub foragers: u32,
+    #[serde(default)]
+    pub trophallaxis_transfers: u32,
+    /// Units of carried food handed from one ant to another.
+    #[serde(default)]
+    pub food_shared: u32,
+    #[serde(default)]
+    pub resting_ants: u32,
+}
+
+impl Stats {
+    pub fn brood(&self) -> u32 {
+        self.eggs + self.larvae + self.pupae
+    }
 }
@@ -11,5 +11,10 @@ pub mod systems;
 pub mod utils;
 
 // Re-export the key data structures and functions to provide a clean public API.
-pub use components::dto::{StatsDto, WorldDto};
+pub use components::dto::{SimulationErrorDto, StatsDto, WorldDto};
+pub use engine::config::SimulationConfig;
+pub use engine::error::SimulationError;
+pub use engine::replay::CommandLog;
+pub use engine::run::{RunSummary, StepSummary, StopCondition};
+pub use engine::schedule::{Schedule, Stage, SystemContext};
 pub use engine::simulation::{Simulation, SimulationOptions};
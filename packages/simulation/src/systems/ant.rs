@@ -1,105 +1,181 @@
 use crate::components::world::{
-    Ant, AntState, FoodPayload, FoodSource, Nest, Position, Target, Velocity,
+    Ant, AntState, CarryCapacity, Caste, FoodPayload, FoodSource, Nest, Position, Role,
+    RouteMemory, Target,
 };
-use crate::engine::config::SIM_CONFIG;
+use crate::engine::config::SimulationConfig;
+use crate::engine::error::SimulationError;
+use crate::engine::events::{Events, SimulationEvent};
 use crate::engine::stats::Stats;
 use crate::utils::maths::target_distance_sq;
 use hecs::{Entity, World};
 use rand::Rng;
 
-pub fn ant_find_food_system(world: &mut World) {
+pub fn ant_find_food_system(
+    world: &mut World,
+    config: &SimulationConfig,
+    events: &mut Events,
+) -> Result<u32, SimulationError> {
     let mut updates = Vec::new();
-    let discovery_radius_sq = SIM_CONFIG.ant.discovery_radius.powi(2);
 
-    let wandering_ants: Vec<(Entity, Position)> = world
-        .query::<(&Position, &AntState, &Ant)>()
+    let wandering_ants: Vec<(Entity, Position, Caste)> = world
+        .query::<(&Position, &AntState, &Ant, Option<&Caste>, Option<&Role>)>()
         .iter()
-        .filter(|&(_, (_, state, _))| *state == AntState::Wandering)
-        .map(|(e, (p, _, _))| (e, *p))
+        .filter(|&(_, (_, state, _, caste, role))| is_looking_for_food(state, caste, role))
+        .map(|(e, (p, _, _, c, _))| (e, *p, c.copied().unwrap_or_default()))
         .collect();
 
-    for (ant_entity, ant_pos) in &wandering_ants {
-        let mut closest_food: Option<(Entity, f32)> = None;
+    for (ant_entity, ant_pos, caste) in &wandering_ants {
+        let discovery_radius_sq = config.caste_traits(*caste).discovery_radius.powi(2);
+        let mut closest_food: Option<(Entity, Position, f32)> = None;
 
         for (food_entity, (food_pos, _)) in world.query::<(&Position, &FoodSource)>().iter() {
             let distance_sq = target_distance_sq(ant_pos.x, ant_pos.y, food_pos.x, food_pos.y);
 
             if distance_sq < discovery_radius_sq {
-                if let Some((_, closest_dist_sq)) = closest_food {
+                if let Some((_, _, closest_dist_sq)) = closest_food {
                     if distance_sq < closest_dist_sq {
-                        closest_food = Some((food_entity, distance_sq));
+                        closest_food = Some((food_entity, *food_pos, distance_sq));
                     }
                 } else {
-                    closest_food = Some((food_entity, distance_sq));
+                    closest_food = Some((food_entity, *food_pos, distance_sq));
                 }
             }
         }
 
-        if let Some((food_entity, _)) = closest_food {
-            updates.push((*ant_entity, food_entity));
+        if let Some((food_entity, food_pos, _)) = closest_food {
+            updates.push((*ant_entity, *ant_pos, food_entity, food_pos));
         }
     }
 
-    for (ant_entity, food_entity) in updates {
+    for (ant_entity, ant_pos, food_entity, food_pos) in updates {
         world
             .insert(ant_entity, (Target(food_entity), AntState::Foraging))
-            .expect("Failed to update ant state in food_discovery_system");
+            .map_err(|_| SimulationError::MissingEntity(ant_entity.id()))?;
+        events.push(SimulationEvent::FoodDiscovered {
+            ant: ant_entity.id(),
+            ant_position: ant_pos.into(),
+            food_source: food_entity.id(),
+            food_position: food_pos.into(),
+        });
     }
+
+    Ok(wandering_ants.len() as u32)
+}
+
+/// Whether an ant in this state picks up food it comes across. Soldiers stay on patrol
+/// and nurses at the nest, so neither does.
+pub(crate) fn is_looking_for_food(
+    state: &AntState,
+    caste: Option<&Caste>,
+    role: Option<&Role>,
+) -> bool {
+    matches!(state, AntState::Wandering | AntState::ReturningToFood)
+        && caste.copied() != Some(Caste::Soldier)
+        && role.copied() != Some(Role::Nurse)
 }
 
-pub fn ant_foraging_system(world: &mut World) {
+pub fn ant_foraging_system(
+    world: &mut World,
+    config: &SimulationConfig,
+    events: &mut Events,
+) -> Result<u32, SimulationError> {
     let mut to_update_to_wandering = Vec::new();
     let mut to_update_to_returning = Vec::new();
-    let arrival_distance_sq = SIM_CONFIG.ant.arrival_distance.powi(2);
+    let arrival_distance_sq = config.ant.arrival_distance.powi(2);
 
     let nest_entity = world
         .query::<&Nest>()
         .iter()
         .next()
-        .expect("No nest entity found")
+        .ok_or(SimulationError::MissingNest)?
         .0;
 
-    let foraging_ants: Vec<(Entity, Position, Entity)> = world
-        .query::<(&Position, &AntState, &Target, &Ant)>()
+    let foraging_ants: Vec<(Entity, Position, Entity, u32)> = world
+        .query::<(
+            &Position,
+            &AntState,
+            &Target,
+            &Ant,
+            Option<&CarryCapacity>,
+            Option<&Caste>,
+        )>()
         .iter()
-        .filter(|(_, (_, state, _, _))| **state == AntState::Foraging)
-        .map(|(e, (p, _, t, _))| (e, *p, t.0))
+        .filter(|(_, (_, state, _, _, _, _))| **state == AntState::Foraging)
+        .map(|(e, (p, _, t, _, capacity, caste))| {
+            let capacity = capacity.map_or_else(
+                || {
+                    config
+                        .caste_traits(caste.copied().unwrap_or_default())
+                        .carry_capacity
+                },
+                |capacity| capacity.0,
+            );
+            (e, *p, t.0, capacity)
+        })
         .collect();
+    let touched = foraging_ants.len() as u32;
 
-    for (ant_entity, ant_pos, target_entity) in foraging_ants {
+    for (ant_entity, ant_pos, target_entity, carry_capacity) in foraging_ants {
         let food_source_exists_and_is_valid = world
             .get::<&FoodSource>(target_entity)
-            .map_or(false, |food| food.amount > 0);
+            .is_ok_and(|food| food.amount > 0);
 
         if !food_source_exists_and_is_valid {
             to_update_to_wandering.push(ant_entity);
             continue;
         }
 
-        let target_pos = *world.get::<&Position>(target_entity).unwrap();
+        let target_pos = *world.get::<&Position>(target_entity).map_err(|_| {
+            SimulationError::DanglingTarget {
+                entity: ant_entity.id(),
+                target: target_entity.id(),
+            }
+        })?;
         let distance_sq = target_distance_sq(ant_pos.x, ant_pos.y, target_pos.x, target_pos.y);
 
         if distance_sq < arrival_distance_sq {
-            to_update_to_returning.push((ant_entity, target_entity));
+            to_update_to_returning.push((ant_entity, target_entity, target_pos, carry_capacity));
         }
     }
 
-    for (ant_entity, food_entity) in to_update_to_returning {
+    for (ant_entity, food_entity, food_pos, carry_capacity) in to_update_to_returning {
         if let Ok(food_source) = world.query_one_mut::<&mut FoodSource>(food_entity) {
             if food_source.amount > 0 {
-                food_source.amount -= SIM_CONFIG.ant.food_payload_amount;
+                // Take what fits, leaving the rest of a nearly empty source behind
+                let amount = carry_capacity.min(food_source.amount);
+                food_source.amount -= amount;
+                events.push(SimulationEvent::FoodPickedUp {
+                    ant: ant_entity.id(),
+                    food_source: food_entity.id(),
+                    amount,
+                    position: food_pos.into(),
+                });
+                let depleted = food_source.amount == 0;
+                if depleted {
+                    events.push(SimulationEvent::FoodSourceDepleted {
+                        food_source: food_entity.id(),
+                        position: food_pos.into(),
+                    });
+                }
+                if depleted || !config.ant.route_memory {
+                    world.remove_one::<RouteMemory>(ant_entity).ok();
+                } else {
+                    world
+                        .insert_one(
+                            ant_entity,
+                            RouteMemory {
+                                position: food_pos,
+                                failed_visits: 0,
+                            },
+                        )
+                        .map_err(|_| SimulationError::MissingEntity(ant_entity.id()))?;
+                }
                 if let Ok(state) = world.query_one_mut::<&mut AntState>(ant_entity) {
                     *state = AntState::ReturningToNest;
                 }
                 world
-                    .insert(
-                        ant_entity,
-                        (
-                            FoodPayload(SIM_CONFIG.ant.food_payload_amount),
-                            Target(nest_entity),
-                        ),
-                    )
-                    .expect("Failed to update ant state in ant_foraging_system");
+                    .insert(ant_entity, (FoodPayload(amount), Target(nest_entity)))
+                    .map_err(|_| SimulationError::MissingEntity(ant_entity.id()))?;
             } else {
                 to_update_to_wandering.push(ant_entity);
             }
@@ -112,12 +188,18 @@ pub fn ant_foraging_system(world: &mut World) {
         }
         world.remove_one::<Target>(entity).ok();
     }
+
+    Ok(touched)
 }
 
-pub fn ant_returning_system(world: &mut World) {
+pub fn ant_returning_system(
+    world: &mut World,
+    config: &SimulationConfig,
+    events: &mut Events,
+) -> u32 {
     let mut to_update_to_wandering = Vec::new();
     let mut food_dropped_at_nest: u32 = 0;
-    let arrival_distance_sq = SIM_CONFIG.ant.arrival_distance.powi(2);
+    let arrival_distance_sq = config.ant.arrival_distance.powi(2);
 
     let returning_ants: Vec<(Entity, Position, Entity, FoodPayload)> = world
         .query::<(&Position, &AntState, &Target, &Ant, &FoodPayload)>()
@@ -125,6 +207,7 @@ pub fn ant_returning_system(world: &mut World) {
         .filter(|(_, (_, state, _, _, _))| **state == AntState::ReturningToNest)
         .map(|(e, (p, _, t, _, fp))| (e, *p, t.0, *fp))
         .collect();
+    let touched = returning_ants.len() as u32;
 
     for (ant_entity, ant_pos, target_entity, food_payload) in returning_ants {
         if let Ok(target_pos) = world.get::<&Position>(target_entity) {
@@ -133,13 +216,25 @@ pub fn ant_returning_system(world: &mut World) {
             if distance_sq < arrival_distance_sq && world.get::<&Nest>(target_entity).is_ok() {
                 to_update_to_wandering.push(ant_entity);
                 food_dropped_at_nest += food_payload.0;
+                events.push(SimulationEvent::FoodDelivered {
+                    ant: ant_entity.id(),
+                    nest: target_entity.id(),
+                    amount: food_payload.0,
+                    position: (*target_pos).into(),
+                });
             }
         }
     }
 
     for entity in to_update_to_wandering {
+        // Ants that remember where the food was head straight back there
+        let next_state = if world.get::<&RouteMemory>(entity).is_ok() {
+            AntState::ReturningToFood
+        } else {
+            AntState::Wandering
+        };
         if let Ok(state) = world.query_one_mut::<&mut AntState>(entity) {
-            *state = AntState::Wandering;
+            *state = next_state;
         }
         world.remove::<(FoodPayload, Target)>(entity).ok();
     }
@@ -147,64 +242,72 @@ pub fn ant_returning_system(world: &mut World) {
     if let Some((_, nest)) = world.query::<&mut Nest>().iter().next() {
         nest.food_store += food_dropped_at_nest;
     }
+    touched
 }
 
-pub fn ant_lifecycle_system(world: &mut World, rng: &mut impl Rng) {
+/// Pick a caste for a new ant, weighted by each caste's spawn weight.
+pub fn random_caste(config: &SimulationConfig, rng: &mut impl Rng) -> Caste {
+    let total: f32 = Caste::ALL
+        .iter()
+        .map(|caste| config.caste(*caste).spawn_weight)
+        .sum();
+    let mut roll = rng.random_range(0.0..total);
+    for caste in Caste::ALL {
+        let weight = config.caste(caste).spawn_weight;
+        if roll < weight {
+            return caste;
+        }
+        roll -= weight;
+    }
+    Caste::Worker
+}
+
+pub fn ant_lifecycle_system(world: &mut World) -> u32 {
+    let mut touched = 0;
     // Decrease health of all ants
     for (_, ant) in world.query_mut::<&mut Ant>() {
+        touched += 1;
         if ant.health > 0 {
             ant.health -= 1;
         }
     }
-
-    // Spawn ants when food store reaches threshold
-    let mut ants_to_spawn: u32 = 0;
-    let mut spawn_pos = Position { x: 0.0, y: 0.0 };
-    for (_, (nest_pos, nest)) in world.query_mut::<(&mut Position, &mut Nest)>() {
-        if nest.food_store >= 100 {
-            ants_to_spawn = nest.food_store / 10;
-            spawn_pos = *nest_pos;
-            nest.food_store -= ants_to_spawn * 10;
-        }
-    }
-
-    world.spawn_batch((0..ants_to_spawn).map(|_| {
-        (
-            spawn_pos,
-            Velocity {
-                dx: rng.random_range(-1.0..1.0),
-                dy: rng.random_range(-1.0..1.0),
-            },
-            AntState::Wandering,
-            Ant {
-                health: rng.random_range(SIM_CONFIG.ant.min_health..SIM_CONFIG.ant.max_health),
-            },
-        )
-    }));
+    touched
 }
 
-pub fn ant_dying_system(world: &mut World, stats: &mut Stats) {
+pub fn ant_dying_system(
+    world: &mut World,
+    config: &SimulationConfig,
+    stats: &mut Stats,
+    events: &mut Events,
+) -> u32 {
+    let mut touched = 0;
     let mut to_update = Vec::new();
 
     // Find all ants with 0 health that are not already dying
-    for (entity, (ant, state)) in world.query::<(&Ant, &AntState)>().iter() {
+    for (entity, (ant, state, pos)) in world.query::<(&Ant, &AntState, &Position)>().iter() {
+        touched += 1;
         if ant.health == 0 {
             if let AntState::Dying(_) = state {
                 // Already dying, do nothing
             } else {
-                to_update.push(entity);
+                to_update.push((entity, *pos));
             }
         }
     }
 
     // Set state to Dying with a countdown timer
-    for entity in to_update {
+    for (entity, pos) in to_update {
         if let Ok(state) = world.query_one_mut::<&mut AntState>(entity) {
-            *state = AntState::Dying(SIM_CONFIG.ant.death_animation_ticks);
+            *state = AntState::Dying(config.ant.death_animation_ticks);
         }
         stats.dead_ants += 1;
         world.remove_one::<Target>(entity).ok();
+        events.push(SimulationEvent::AntStartedDying {
+            ant: entity.id(),
+            position: pos.into(),
+        });
     }
+    touched
 }
 
 #[cfg(test)]
@@ -225,12 +328,26 @@ mod tests {
             Position { x: 10.0, y: 10.0 },
             Velocity { dx: 0.0, dy: 0.0 },
             AntState::Wandering,
-            Ant { health: 100 },
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+        ));
+        let food_entity = world.spawn((
+            Position { x: 12.0, y: 12.0 },
+            FoodSource {
+                amount: 100,
+                birth_tick: 0,
+            },
         ));
-        let food_entity = world.spawn((Position { x: 12.0, y: 12.0 }, FoodSource { amount: 100 }));
 
         // 2. Action
-        ant_find_food_system(&mut world);
+        ant_find_food_system(
+            &mut world,
+            &SimulationConfig::default(),
+            &mut Events::default(),
+        )
+        .unwrap();
 
         // 3. Assertion
         let target = world.get::<&Target>(ant_entity).unwrap();
@@ -245,17 +362,31 @@ mod tests {
         // 1. Setup
         let mut world = World::new();
         world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
-        let food_entity = world.spawn((Position { x: 10.0, y: 10.0 }, FoodSource { amount: 100 }));
+        let food_entity = world.spawn((
+            Position { x: 10.0, y: 10.0 },
+            FoodSource {
+                amount: 100,
+                birth_tick: 0,
+            },
+        ));
         let ant_entity = world.spawn((
             Position { x: 9.9, y: 9.9 },
             AntState::Foraging,
             Target(food_entity),
-            Ant { health: 100 },
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
         ));
 
         // 2. Action
         world.despawn(food_entity).unwrap();
-        ant_foraging_system(&mut world);
+        ant_foraging_system(
+            &mut world,
+            &SimulationConfig::default(),
+            &mut Events::default(),
+        )
+        .unwrap();
 
         // 3. Assertion
         let ant_state = world.get::<&AntState>(ant_entity).unwrap();
@@ -268,16 +399,30 @@ mod tests {
         // 1. Setup
         let mut world = World::new();
         world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
-        let food_entity = world.spawn((Position { x: 10.0, y: 10.0 }, FoodSource { amount: 0 }));
+        let food_entity = world.spawn((
+            Position { x: 10.0, y: 10.0 },
+            FoodSource {
+                amount: 0,
+                birth_tick: 0,
+            },
+        ));
         let ant_entity = world.spawn((
             Position { x: 9.9, y: 9.9 },
             AntState::Foraging,
             Target(food_entity),
-            Ant { health: 100 },
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
         ));
 
         // 2. Action
-        ant_foraging_system(&mut world);
+        ant_foraging_system(
+            &mut world,
+            &SimulationConfig::default(),
+            &mut Events::default(),
+        )
+        .unwrap();
 
         // 3. Assertion
         let ant_state = world.get::<&AntState>(ant_entity).unwrap();
@@ -290,16 +435,30 @@ mod tests {
         // 1. Setup
         let mut world = World::new();
         let nest_entity = world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
-        let food_entity = world.spawn((Position { x: 10.0, y: 10.0 }, FoodSource { amount: 100 }));
+        let food_entity = world.spawn((
+            Position { x: 10.0, y: 10.0 },
+            FoodSource {
+                amount: 100,
+                birth_tick: 0,
+            },
+        ));
         let ant_entity = world.spawn((
             Position { x: 9.9, y: 9.9 },
             AntState::Foraging,
             Target(food_entity),
-            Ant { health: 100 },
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
         ));
 
         // 2. Action
-        ant_foraging_system(&mut world);
+        ant_foraging_system(
+            &mut world,
+            &SimulationConfig::default(),
+            &mut Events::default(),
+        )
+        .unwrap();
 
         // 3. Assertion
         let ant_state = world.get::<&AntState>(ant_entity).unwrap();
@@ -315,6 +474,200 @@ mod tests {
         assert_eq!(target.0, nest_entity);
     }
 
+    #[test]
+    fn test_ant_foraging_system_returns_error_without_nest() {
+        // 1. Setup
+        let mut world = World::new();
+        let food_entity = world.spawn((
+            Position { x: 10.0, y: 10.0 },
+            FoodSource {
+                amount: 100,
+                birth_tick: 0,
+            },
+        ));
+        world.spawn((
+            Position { x: 9.9, y: 9.9 },
+            AntState::Foraging,
+            Target(food_entity),
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+        ));
+
+        // 2. Action
+        let result = ant_foraging_system(
+            &mut world,
+            &SimulationConfig::default(),
+            &mut Events::default(),
+        );
+
+        // 3. Assertion
+        assert_eq!(result, Err(SimulationError::MissingNest));
+    }
+
+    #[test]
+    fn test_ant_foraging_system_reports_pickup_and_depletion() {
+        // 1. Setup
+        let mut world = World::new();
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let food_entity = world.spawn((
+            Position { x: 10.0, y: 10.0 },
+            FoodSource {
+                amount: 10,
+                birth_tick: 0,
+            },
+        ));
+        let ant_entity = world.spawn((
+            Position { x: 9.9, y: 9.9 },
+            AntState::Foraging,
+            Target(food_entity),
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+        ));
+
+        // 2. Action
+        let mut events = Events::default();
+        ant_foraging_system(&mut world, &SimulationConfig::default(), &mut events).unwrap();
+
+        // 3. Assertion
+        let food_position = Position { x: 10.0, y: 10.0 }.into();
+        assert_eq!(
+            events.drain(),
+            vec![
+                SimulationEvent::FoodPickedUp {
+                    ant: ant_entity.id(),
+                    food_source: food_entity.id(),
+                    amount: 10,
+                    position: food_position,
+                },
+                SimulationEvent::FoodSourceDepleted {
+                    food_source: food_entity.id(),
+                    position: food_position,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ant_foraging_system_takes_only_what_is_left() {
+        // 1. Setup
+        let mut world = World::new();
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let food_entity = world.spawn((
+            Position { x: 10.0, y: 10.0 },
+            FoodSource {
+                amount: 3,
+                birth_tick: 0,
+            },
+        ));
+        let ant_entity = world.spawn((
+            Position { x: 9.9, y: 9.9 },
+            AntState::Foraging,
+            Target(food_entity),
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            CarryCapacity(12),
+        ));
+
+        // 2. Action
+        let mut events = Events::default();
+        ant_foraging_system(&mut world, &SimulationConfig::default(), &mut events).unwrap();
+
+        // 3. Assertion
+        assert_eq!(world.get::<&FoodSource>(food_entity).unwrap().amount, 0);
+        assert_eq!(
+            *world.get::<&FoodPayload>(ant_entity).unwrap(),
+            FoodPayload(3)
+        );
+        assert!(
+            events
+                .iter()
+                .any(|event| matches!(event, SimulationEvent::FoodPickedUp { amount: 3, .. }))
+        );
+    }
+
+    #[test]
+    fn test_ant_foraging_system_remembers_source_until_it_is_empty() {
+        // 1. Setup
+        let mut world = World::new();
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let food_pos = Position { x: 10.0, y: 10.0 };
+        let food_entity = world.spawn((
+            food_pos,
+            FoodSource {
+                amount: 15,
+                birth_tick: 0,
+            },
+        ));
+        let spawn_forager = |world: &mut World| {
+            world.spawn((
+                Position { x: 9.9, y: 9.9 },
+                AntState::Foraging,
+                Target(food_entity),
+                Ant {
+                    health: 100,
+                    birth_tick: 0,
+                },
+            ))
+        };
+        let first = spawn_forager(&mut world);
+        let second = spawn_forager(&mut world);
+        let config = SimulationConfig::default();
+
+        // 2. Action
+        ant_foraging_system(&mut world, &config, &mut Events::default()).unwrap();
+
+        // 3. Assertion
+        assert_eq!(
+            *world.get::<&RouteMemory>(first).unwrap(),
+            RouteMemory {
+                position: food_pos,
+                failed_visits: 0,
+            }
+        );
+        assert!(world.get::<&RouteMemory>(second).is_err());
+    }
+
+    #[test]
+    fn test_ant_returning_system_sends_ants_back_to_remembered_food() {
+        // 1. Setup
+        let mut world = World::new();
+        let nest_entity = world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let ant_entity = world.spawn((
+            Position { x: 0.1, y: 0.1 },
+            AntState::ReturningToNest,
+            FoodPayload(10),
+            Target(nest_entity),
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            RouteMemory {
+                position: Position { x: 50.0, y: 50.0 },
+                failed_visits: 0,
+            },
+        ));
+
+        // 2. Action
+        ant_returning_system(
+            &mut world,
+            &SimulationConfig::default(),
+            &mut Events::default(),
+        );
+
+        // 3. Assertion
+        assert_eq!(
+            *world.get::<&AntState>(ant_entity).unwrap(),
+            AntState::ReturningToFood
+        );
+        assert!(world.get::<&Target>(ant_entity).is_err());
+    }
+
     #[test]
     fn test_ant_returning_system_updates_ant_to_wandering_at_nest() {
         // 1. Setup
@@ -325,13 +678,26 @@ mod tests {
             AntState::ReturningToNest,
             FoodPayload(10),
             Target(nest_entity),
-            Ant { health: 100 },
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
         ));
 
         // 2. Action
-        ant_returning_system(&mut world);
+        let mut events = Events::default();
+        ant_returning_system(&mut world, &SimulationConfig::default(), &mut events);
 
         // 3. Assertion
+        assert_eq!(
+            events.drain(),
+            vec![SimulationEvent::FoodDelivered {
+                ant: ant_entity.id(),
+                nest: nest_entity.id(),
+                amount: 10,
+                position: Position { x: 0.0, y: 0.0 }.into(),
+            }]
+        );
         let ant_state = world.get::<&AntState>(ant_entity).unwrap();
         assert_eq!(*ant_state, AntState::Wandering);
         assert!(world.get::<&FoodPayload>(ant_entity).is_err());
@@ -342,23 +708,28 @@ mod tests {
     #[test]
     fn test_ant_lifecycle_system_decreases_health_of_all_ants() {
         // 1. Setup
-        let mut rng = StdRng::seed_from_u64(42);
         let mut world = World::new();
         let ant_entity = world.spawn((
             Position { x: 10.0, y: 10.0 },
             Velocity { dx: 0.0, dy: 0.0 },
             AntState::Wandering,
-            Ant { health: 100 },
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
         ));
         let ant_entity2 = world.spawn((
             Position { x: 10.0, y: 10.0 },
             Velocity { dx: 0.0, dy: 0.0 },
             AntState::Foraging,
-            Ant { health: 100 },
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
         ));
 
         // 2. Action
-        ant_lifecycle_system(&mut world, &mut rng);
+        ant_lifecycle_system(&mut world);
 
         // 3. Assertion
         let ant = world.get::<&Ant>(ant_entity).unwrap();
@@ -369,25 +740,55 @@ mod tests {
     }
 
     #[test]
-    fn test_ant_lifecycle_system_spawns_new_ants() {
+    fn test_ant_find_food_system_ignores_soldiers() {
         // 1. Setup
-        let mut rng = StdRng::seed_from_u64(42);
         let mut world = World::new();
-
-        world.spawn((Position { x: 0.0, y: 0.0 }, Nest { food_store: 150 }));
-
-        world.spawn((
+        let soldier = world.spawn((
             Position { x: 10.0, y: 10.0 },
-            Velocity { dx: 0.0, dy: 0.0 },
             AntState::Wandering,
-            Ant { health: 100 },
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Caste::Soldier,
+        ));
+        world.spawn((
+            Position { x: 12.0, y: 12.0 },
+            FoodSource {
+                amount: 100,
+                birth_tick: 0,
+            },
         ));
 
         // 2. Action
-        ant_lifecycle_system(&mut world, &mut rng);
+        ant_find_food_system(
+            &mut world,
+            &SimulationConfig::default(),
+            &mut Events::default(),
+        )
+        .unwrap();
+
+        // 3. Assertion
+        assert_eq!(
+            *world.get::<&AntState>(soldier).unwrap(),
+            AntState::Wandering
+        );
+        assert!(world.get::<&Target>(soldier).is_err());
+    }
+
+    #[test]
+    fn test_random_caste_follows_spawn_weights() {
+        // 1. Setup
+        let mut config = SimulationConfig::default();
+        config.castes.worker.spawn_weight = 0.0;
+        config.castes.soldier.spawn_weight = 0.0;
+        config.castes.scout.spawn_weight = 1.0;
+        let mut rng = StdRng::seed_from_u64(42);
+
+        // 2. Action
+        let castes: Vec<Caste> = (0..50).map(|_| random_caste(&config, &mut rng)).collect();
 
         // 3. Assertion
-        let ant_count = world.query::<(&Position, &Ant)>().iter().count();
-        assert_eq!(ant_count, 16);
+        assert!(castes.iter().all(|caste| *caste == Caste::Scout));
     }
 }
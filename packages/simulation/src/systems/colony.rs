@@ -0,0 +1,431 @@
+use crate::components::world::{
+    Ant, AntState, Brood, BroodStage, CarryCapacity, Caste, Nest, Position, Queen, Role, Satiety,
+    Stamina, Velocity,
+};
+use crate::engine::config::SimulationConfig;
+use crate::engine::events::{Events, SimulationEvent};
+use crate::systems::ant::random_caste;
+use hecs::{Entity, World};
+use rand::Rng;
+use std::cmp::Reverse;
+
+/// Ages the queen and lays an egg when one is due and the nest can pay for it. Once the
+/// queen has died no more eggs are laid.
+pub fn queen_system(
+    world: &mut World,
+    config: &SimulationConfig,
+    tick: u32,
+    events: &mut Events,
+) -> u32 {
+    let mut dead_queens = Vec::new();
+    let mut laying_positions = Vec::new();
+
+    for (entity, (queen, pos)) in world.query_mut::<(&mut Queen, &Position)>() {
+        queen.health = queen.health.saturating_sub(1);
+        if queen.health == 0 {
+            dead_queens.push((entity, *pos));
+        } else {
+            laying_positions.push(*pos);
+        }
+    }
+    let touched = (dead_queens.len() + laying_positions.len()) as u32;
+
+    for (entity, pos) in dead_queens {
+        world.despawn(entity).ok();
+        events.push(SimulationEvent::QueenDied {
+            queen: entity.id(),
+            position: pos.into(),
+        });
+    }
+
+    if !tick.is_multiple_of(config.colony.egg_interval_ticks) {
+        return touched;
+    }
+
+    for pos in laying_positions {
+        let egg_food_cost = config.colony.egg_food_cost;
+        let paid = world
+            .query_mut::<&mut Nest>()
+            .into_iter()
+            .next()
+            .is_some_and(|(_, nest)| {
+                if nest.food_store >= egg_food_cost {
+                    nest.food_store -= egg_food_cost;
+                    true
+                } else {
+                    false
+                }
+            });
+        if !paid {
+            break;
+        }
+
+        let egg = world.spawn((pos, Brood::egg()));
+        events.push(SimulationEvent::EggLaid {
+            egg: egg.id(),
+            position: pos.into(),
+        });
+    }
+    touched
+}
+
+/// Moves brood through its stages. Larvae are fed from the nest store one unit per tick
+/// and cannot pupate until they have eaten enough; pupae emerge as adult ants.
+pub fn brood_development_system(
+    world: &mut World,
+    config: &SimulationConfig,
+    tick: u32,
+    events: &mut Events,
+    rng: &mut impl Rng,
+) -> u32 {
+    let colony = &config.colony;
+    let mut food_available = world
+        .query::<&Nest>()
+        .iter()
+        .next()
+        .map_or(0, |(_, nest)| nest.food_store);
+    let mut food_fed: u32 = 0;
+    let mut emerged = Vec::new();
+    let mut touched = 0;
+
+    for (entity, (brood, pos)) in world.query_mut::<(&mut Brood, &Position)>() {
+        touched += 1;
+        brood.ticks_in_stage += 1;
+        match brood.stage {
+            BroodStage::Egg => {
+                if brood.ticks_in_stage >= colony.egg_ticks {
+                    brood.stage = BroodStage::Larva;
+                    brood.ticks_in_stage = 0;
+                }
+            }
+            BroodStage::Larva => {
+                if brood.food_eaten < colony.larva_food && food_available > 0 {
+                    food_available -= 1;
+                    food_fed += 1;
+                    brood.food_eaten += 1;
+                }
+                if brood.ticks_in_stage >= colony.larva_ticks
+                    && brood.food_eaten >= colony.larva_food
+                {
+                    brood.stage = BroodStage::Pupa;
+                    brood.ticks_in_stage = 0;
+                }
+            }
+            BroodStage::Pupa => {
+                if brood.ticks_in_stage >= colony.pupa_ticks {
+                    emerged.push((entity, *pos));
+                }
+            }
+        }
+    }
+
+    if food_fed > 0
+        && let Some((_, nest)) = world.query_mut::<&mut Nest>().into_iter().next()
+    {
+        nest.food_store -= food_fed;
+    }
+
+    for (brood_entity, pos) in emerged {
+        world.despawn(brood_entity).ok();
+        let caste = random_caste(config, rng);
+        let traits = config.caste_traits(caste);
+        let ant = world.spawn((
+            pos,
+            Velocity {
+                dx: rng.random_range(-1.0..1.0),
+                dy: rng.random_range(-1.0..1.0),
+            },
+            AntState::Wandering,
+            Ant {
+                health: rng.random_range(traits.min_health..traits.max_health),
+                birth_tick: tick,
+            },
+            caste,
+            Satiety(config.hunger.max_satiety),
+            CarryCapacity(traits.carry_capacity),
+            Stamina(config.fatigue.max_stamina),
+        ));
+        if caste == Caste::Worker && config.roles.age_polyethism {
+            world.insert_one(ant, Role::Nurse).ok();
+        }
+        events.push(SimulationEvent::AntHatched {
+            ant: ant.id(),
+            position: pos.into(),
+        });
+    }
+    touched
+}
+
+/// Moves nurses on to foraging once they reach `forager_age`. While foragers make up
+/// too small a share of the workers, the oldest nurses past `early_forager_age` switch
+/// early until the share is made up.
+pub fn task_allocation_system(
+    world: &mut World,
+    config: &SimulationConfig,
+    tick: u32,
+    events: &mut Events,
+) -> u32 {
+    let roles = &config.roles;
+    let mut foragers = 0;
+    let mut nurses: Vec<(Entity, u32, Position)> = Vec::new();
+    for (entity, (ant, state, pos, caste, role)) in world
+        .query::<(&Ant, &AntState, &Position, Option<&Caste>, Option<&Role>)>()
+        .iter()
+    {
+        if matches!(state, AntState::Dying(_))
+            || caste.copied().unwrap_or_default() != Caste::Worker
+        {
+            continue;
+        }
+        match role.copied().unwrap_or_default() {
+            Role::Nurse => nurses.push((entity, tick.saturating_sub(ant.birth_tick), *pos)),
+            Role::Forager => foragers += 1,
+        }
+    }
+
+    let touched = (foragers + nurses.len()) as u32;
+    let workers = (foragers + nurses.len()) as f64;
+    nurses.sort_by_key(|(_, age, _)| Reverse(*age));
+    for (entity, age, pos) in nurses {
+        let short_of_foragers = (foragers as f64) < roles.min_forager_share * workers;
+        let switches = !roles.age_polyethism
+            || age >= roles.forager_age
+            || (short_of_foragers && age >= roles.early_forager_age);
+        // Nurses are oldest first, so no younger one switches either
+        if !switches {
+            break;
+        }
+
+        if let Ok(role) = world.query_one_mut::<&mut Role>(entity) {
+            *role = Role::Forager;
+        }
+        foragers += 1;
+        events.push(SimulationEvent::AntBecameForager {
+            ant: entity.id(),
+            age,
+            position: pos.into(),
+        });
+    }
+    touched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_queen_system_lays_eggs_only_when_nest_has_food() {
+        // 1. Setup
+        let mut world = World::new();
+        let nest = world.spawn((Position { x: 0.0, y: 0.0 }, Nest { food_store: 3 }));
+        world.spawn((Position { x: 0.0, y: 0.0 }, Queen { health: 100 }));
+        let config = SimulationConfig::default();
+        let mut events = Events::default();
+
+        // 2. Action
+        for tick in 1..=config.colony.egg_interval_ticks * 3 {
+            queen_system(&mut world, &config, tick, &mut events);
+        }
+
+        // 3. Assertion
+        assert_eq!(world.query::<&Brood>().iter().count(), 1);
+        assert_eq!(world.get::<&Nest>(nest).unwrap().food_store, 1);
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn test_queen_system_stops_laying_after_queen_dies() {
+        // 1. Setup
+        let mut world = World::new();
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest { food_store: 1000 }));
+        world.spawn((Position { x: 0.0, y: 0.0 }, Queen { health: 2 }));
+        let config = SimulationConfig::default();
+        let mut events = Events::default();
+
+        // 2. Action
+        for tick in 1..=config.colony.egg_interval_ticks * 3 {
+            queen_system(&mut world, &config, tick, &mut events);
+        }
+
+        // 3. Assertion
+        assert_eq!(world.query::<&Queen>().iter().count(), 0);
+        assert_eq!(world.query::<&Brood>().iter().count(), 0);
+        assert!(matches!(
+            events.drain()[..],
+            [SimulationEvent::QueenDied { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_brood_development_system_raises_an_adult_from_an_egg() {
+        // 1. Setup
+        let mut world = World::new();
+        let mut rng = StdRng::seed_from_u64(42);
+        let config = SimulationConfig::default();
+        let nest = world.spawn((Position { x: 0.0, y: 0.0 }, Nest { food_store: 100 }));
+        world.spawn((Position { x: 0.0, y: 0.0 }, Brood::egg()));
+        let colony = &config.colony;
+        let ticks = colony.egg_ticks + colony.larva_ticks + colony.pupa_ticks;
+        let mut events = Events::default();
+
+        // 2. Action
+        for tick in 1..ticks {
+            brood_development_system(&mut world, &config, tick, &mut events, &mut rng);
+        }
+        let before_emerging = world.query::<&Ant>().iter().count();
+        brood_development_system(&mut world, &config, ticks, &mut events, &mut rng);
+
+        // 3. Assertion
+        assert_eq!(before_emerging, 0);
+        assert_eq!(world.query::<&Brood>().iter().count(), 0);
+        let ants: Vec<u32> = world
+            .query::<&Ant>()
+            .iter()
+            .map(|(_, ant)| ant.birth_tick)
+            .collect();
+        assert_eq!(ants, vec![ticks]);
+        assert_eq!(
+            world.get::<&Nest>(nest).unwrap().food_store,
+            100 - colony.larva_food
+        );
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn test_brood_development_system_holds_unfed_larvae() {
+        // 1. Setup
+        let mut world = World::new();
+        let mut rng = StdRng::seed_from_u64(42);
+        let config = SimulationConfig::default();
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let larva = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            Brood {
+                stage: BroodStage::Larva,
+                ticks_in_stage: 0,
+                food_eaten: 0,
+            },
+        ));
+
+        // 2. Action
+        for tick in 1..=config.colony.larva_ticks * 2 {
+            brood_development_system(&mut world, &config, tick, &mut Events::default(), &mut rng);
+        }
+
+        // 3. Assertion
+        let brood = world.get::<&Brood>(larva).unwrap();
+        assert_eq!(brood.stage, BroodStage::Larva);
+        assert_eq!(brood.food_eaten, 0);
+    }
+
+    #[test]
+    fn test_task_allocation_system_turns_nurses_into_foragers_with_age() {
+        // 1. Setup
+        let mut world = World::new();
+        let mut config = SimulationConfig::default();
+        config.roles.min_forager_share = 0.0;
+        let mut events = Events::default();
+        let old = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Caste::Worker,
+            Role::Nurse,
+        ));
+        let young = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 350,
+            },
+            Caste::Worker,
+            Role::Nurse,
+        ));
+
+        // 2. Action
+        task_allocation_system(&mut world, &config, config.roles.forager_age, &mut events);
+
+        // 3. Assertion
+        assert_eq!(*world.get::<&Role>(old).unwrap(), Role::Forager);
+        assert_eq!(*world.get::<&Role>(young).unwrap(), Role::Nurse);
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn test_task_allocation_system_switches_oldest_nurses_early_when_short_of_foragers() {
+        // 1. Setup
+        let mut world = World::new();
+        let config = SimulationConfig::default();
+        let mut events = Events::default();
+        let tick = 200;
+        let oldest = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Caste::Worker,
+            Role::Nurse,
+        ));
+        let older = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 50,
+            },
+            Caste::Worker,
+            Role::Nurse,
+        ));
+        let infant = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 150,
+            },
+            Caste::Worker,
+            Role::Nurse,
+        ));
+        for _ in 0..3 {
+            world.spawn((
+                Position { x: 0.0, y: 0.0 },
+                AntState::Wandering,
+                Ant {
+                    health: 100,
+                    birth_tick: tick,
+                },
+                Caste::Worker,
+                Role::Nurse,
+            ));
+        }
+        for _ in 0..2 {
+            world.spawn((
+                Position { x: 0.0, y: 0.0 },
+                AntState::Wandering,
+                Ant {
+                    health: 100,
+                    birth_tick: 0,
+                },
+                Caste::Worker,
+                Role::Forager,
+            ));
+        }
+
+        // 2. Action
+        task_allocation_system(&mut world, &config, tick, &mut events);
+
+        // 3. Assertion
+        // Three foragers out of eight workers make up the 30% share
+        assert_eq!(*world.get::<&Role>(oldest).unwrap(), Role::Forager);
+        assert_eq!(*world.get::<&Role>(older).unwrap(), Role::Nurse);
+        assert_eq!(*world.get::<&Role>(infant).unwrap(), Role::Nurse);
+    }
+}
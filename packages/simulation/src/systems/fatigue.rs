@@ -0,0 +1,322 @@
+use crate::components::world::{
+    Ant, AntState, Caste, FoodPayload, Nest, Position, RouteMemory, Stamina, Target, Velocity,
+};
+use crate::engine::config::SimulationConfig;
+use crate::engine::events::{Events, SimulationEvent};
+use crate::systems::movement::distance_walked;
+use crate::utils::maths::target_distance_sq;
+use hecs::{Entity, World};
+
+/// Drains stamina by how far each ant walks and whether it is carrying food, and
+/// restores it while the ant rests. Exhausted ants that are out and about head for the
+/// nest and lie down once they get there; rested ants get back to work. With fatigue
+/// turned off, resting ants and ants going home to rest get back to work at once.
+pub fn fatigue_system(world: &mut World, config: &SimulationConfig, events: &mut Events) -> u32 {
+    let fatigue = &config.fatigue;
+    if !fatigue.fatigue {
+        return release_tired_ants(world);
+    }
+    let Some((nest_entity, nest_pos)) = world
+        .query::<(&Position, &Nest)>()
+        .iter()
+        .next()
+        .map(|(entity, (pos, _))| (entity, *pos))
+    else {
+        return 0;
+    };
+    let arrival_distance_sq = config.ant.arrival_distance.powi(2);
+
+    let mut touched = 0;
+    let mut heading_home = Vec::new();
+    let mut lying_down = Vec::new();
+    let mut getting_up = Vec::new();
+    for (entity, (_, stamina, state, pos, vel, caste, payload, memory)) in world.query_mut::<(
+        &Ant,
+        &mut Stamina,
+        &AntState,
+        &Position,
+        Option<&Velocity>,
+        Option<&Caste>,
+        Option<&FoodPayload>,
+        Option<&RouteMemory>,
+    )>() {
+        touched += 1;
+        if *state == AntState::Resting {
+            stamina.0 = (stamina.0 + fatigue.recovery_rate).min(fatigue.max_stamina);
+            if stamina.0 >= fatigue.rested_threshold {
+                getting_up.push((entity, back_to_work(memory)));
+            }
+            continue;
+        }
+        if matches!(state, AntState::Dying(_)) {
+            continue;
+        }
+
+        let walked = distance_walked(config, vel, caste, Some(&*stamina));
+        let mut drain = fatigue.walking_drain * walked;
+        if payload.is_some() {
+            drain += fatigue.carrying_drain;
+        }
+        let was_exhausted = stamina.0 < fatigue.exhausted_threshold;
+        stamina.0 = (stamina.0 - drain).max(0.0);
+        if stamina.0 >= fatigue.exhausted_threshold {
+            continue;
+        }
+        if !was_exhausted {
+            events.push(SimulationEvent::AntExhausted {
+                ant: entity.id(),
+                position: (*pos).into(),
+            });
+        }
+
+        let at_nest =
+            target_distance_sq(pos.x, pos.y, nest_pos.x, nest_pos.y) < arrival_distance_sq;
+        match state {
+            AntState::Wandering | AntState::ReturningToFood if at_nest => lying_down.push(entity),
+            AntState::Wandering | AntState::ReturningToFood => heading_home.push(entity),
+            AntState::ReturningToNest if at_nest && payload.is_none() => lying_down.push(entity),
+            // Ants carrying food or going home to eat are already on their way
+            _ => {}
+        }
+    }
+
+    for entity in heading_home {
+        world
+            .insert(entity, (AntState::ReturningToNest, Target(nest_entity)))
+            .ok();
+    }
+    for entity in lying_down {
+        world
+            .insert(entity, (AntState::Resting, Velocity { dx: 0.0, dy: 0.0 }))
+            .ok();
+        world.remove_one::<Target>(entity).ok();
+    }
+    for (entity, next_state) in getting_up {
+        world.insert_one(entity, next_state).ok();
+    }
+    touched
+}
+
+/// Gets ants that are resting, or on their way home to rest, back to work.
+fn release_tired_ants(world: &mut World) -> u32 {
+    let tired: Vec<(Entity, AntState)> = world
+        .query::<(&AntState, Option<&FoodPayload>, Option<&RouteMemory>)>()
+        .with::<&Ant>()
+        .iter()
+        .filter(|(_, (state, payload, _))| {
+            **state == AntState::Resting
+                || (**state == AntState::ReturningToNest && payload.is_none())
+        })
+        .map(|(entity, (_, _, memory))| (entity, back_to_work(memory)))
+        .collect();
+    let touched = tired.len() as u32;
+
+    for (entity, next_state) in tired {
+        world.insert_one(entity, next_state).ok();
+        world.remove_one::<Target>(entity).ok();
+    }
+    touched
+}
+
+/// What an ant does once it no longer needs to rest: head back to the food it
+/// remembers, or look for some.
+fn back_to_work(memory: Option<&RouteMemory>) -> AntState {
+    if memory.is_some() {
+        AntState::ReturningToFood
+    } else {
+        AntState::Wandering
+    }
+}
+
+/// Whether an ant with this much stamina left is too tired to move at full speed.
+pub(crate) fn is_exhausted(stamina: Option<&Stamina>, config: &SimulationConfig) -> bool {
+    config.fatigue.fatigue
+        && stamina.is_some_and(|stamina| stamina.0 < config.fatigue.exhausted_threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_fatigue_system_sends_exhausted_ants_home_to_rest() {
+        // 1. Setup
+        let mut world = World::new();
+        let config = SimulationConfig::default();
+        let mut events = Events::default();
+        let nest = world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let far = world.spawn((
+            Position { x: 50.0, y: 0.0 },
+            Velocity { dx: 1.0, dy: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Stamina(20.1),
+        ));
+        let home = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            Velocity { dx: 1.0, dy: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Stamina(20.1),
+        ));
+        let fresh = world.spawn((
+            Position { x: 50.0, y: 0.0 },
+            Velocity { dx: 1.0, dy: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Stamina(100.0),
+        ));
+
+        // 2. Action
+        fatigue_system(&mut world, &config, &mut events);
+
+        // 3. Assertion
+        assert_eq!(
+            *world.get::<&AntState>(far).unwrap(),
+            AntState::ReturningToNest
+        );
+        assert_eq!(world.get::<&Target>(far).unwrap().0, nest);
+        assert_eq!(*world.get::<&AntState>(home).unwrap(), AntState::Resting);
+        let home_vel = world.get::<&Velocity>(home).unwrap();
+        assert_eq!((home_vel.dx, home_vel.dy), (0.0, 0.0));
+        assert_eq!(*world.get::<&AntState>(fresh).unwrap(), AntState::Wandering);
+        assert_eq!(events.len(), 2);
+    }
+
+    #[test]
+    fn test_fatigue_system_wakes_ants_once_rested() {
+        // 1. Setup
+        let mut world = World::new();
+        let config = SimulationConfig::default();
+        let mut events = Events::default();
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let tired = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            Velocity { dx: 1.0, dy: 0.0 },
+            AntState::Resting,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Stamina(50.0),
+        ));
+        let rested = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            Velocity { dx: 1.0, dy: 0.0 },
+            AntState::Resting,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Stamina(89.5),
+        ));
+
+        // 2. Action
+        fatigue_system(&mut world, &config, &mut events);
+
+        // 3. Assertion
+        assert_eq!(world.get::<&Stamina>(tired).unwrap().0, 51.0);
+        assert_eq!(*world.get::<&AntState>(tired).unwrap(), AntState::Resting);
+        assert_eq!(
+            *world.get::<&AntState>(rested).unwrap(),
+            AntState::Wandering
+        );
+    }
+
+    #[test]
+    fn test_fatigue_system_drains_by_distance_actually_walked() {
+        // 1. Setup
+        let mut world = World::new();
+        let config = SimulationConfig::default();
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let walking = world.spawn((
+            Position { x: 50.0, y: 0.0 },
+            Velocity { dx: 1.0, dy: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Stamina(50.0),
+        ));
+        let held = world.spawn((
+            Position { x: 50.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::ReturningToFood,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Stamina(50.0),
+        ));
+
+        // 2. Action
+        fatigue_system(&mut world, &config, &mut Events::default());
+
+        // 3. Assertion
+        assert!(world.get::<&Stamina>(walking).unwrap().0 < 50.0);
+        assert_eq!(world.get::<&Stamina>(held).unwrap().0, 50.0);
+    }
+
+    #[test]
+    fn test_fatigue_system_releases_tired_ants_when_turned_off() {
+        // 1. Setup
+        let mut world = World::new();
+        let mut config = SimulationConfig::default();
+        config.fatigue.fatigue = false;
+        let nest = world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let ant = Ant {
+            health: 100,
+            birth_tick: 0,
+        };
+        let resting = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            AntState::Resting,
+            ant.clone(),
+            RouteMemory {
+                position: Position { x: 50.0, y: 0.0 },
+                failed_visits: 0,
+            },
+        ));
+        let heading_home = world.spawn((
+            Position { x: 50.0, y: 0.0 },
+            AntState::ReturningToNest,
+            ant.clone(),
+            Target(nest),
+        ));
+        let carrying = world.spawn((
+            Position { x: 50.0, y: 0.0 },
+            AntState::ReturningToNest,
+            ant,
+            Target(nest),
+            FoodPayload(1),
+        ));
+
+        // 2. Action
+        fatigue_system(&mut world, &config, &mut Events::default());
+
+        // 3. Assertion
+        assert_eq!(
+            *world.get::<&AntState>(resting).unwrap(),
+            AntState::ReturningToFood
+        );
+        assert_eq!(
+            *world.get::<&AntState>(heading_home).unwrap(),
+            AntState::Wandering
+        );
+        assert!(world.get::<&Target>(heading_home).is_err());
+        assert_eq!(
+            *world.get::<&AntState>(carrying).unwrap(),
+            AntState::ReturningToNest
+        );
+        assert!(world.get::<&Target>(carrying).is_ok());
+    }
+}
@@ -0,0 +1,298 @@
+use crate::components::world::{
+    Ant, AntState, Caste, FoodPayload, Nest, Position, Satiety, Stamina, Target, Velocity,
+};
+use crate::engine::config::SimulationConfig;
+use crate::engine::error::SimulationError;
+use crate::engine::events::{Events, SimulationEvent};
+use crate::engine::stats::Stats;
+use crate::systems::movement::distance_walked;
+use crate::utils::maths::target_distance_sq;
+use hecs::World;
+
+/// Drains satiety by how far each ant walks and whether it is carrying food. Ants that
+/// run out are left with no health, so `ant_dying_system` takes them from there.
+pub fn hunger_system(
+    world: &mut World,
+    config: &SimulationConfig,
+    stats: &mut Stats,
+    events: &mut Events,
+) -> u32 {
+    let hunger = &config.hunger;
+    let mut touched = 0;
+
+    for (entity, (ant, satiety, state, pos, vel, caste, stamina, payload)) in world.query_mut::<(
+        &mut Ant,
+        &mut Satiety,
+        &AntState,
+        &Position,
+        Option<&Velocity>,
+        Option<&Caste>,
+        Option<&Stamina>,
+        Option<&FoodPayload>,
+    )>() {
+        if ant.health == 0 || matches!(state, AntState::Dying(_)) {
+            continue;
+        }
+        touched += 1;
+
+        let walked = distance_walked(config, vel, caste, stamina);
+        let mut drain = hunger.base_drain + hunger.walking_drain * walked;
+        if payload.is_some() {
+            drain += hunger.carrying_drain;
+        }
+
+        satiety.0 = (satiety.0 - drain).max(0.0);
+        if satiety.0 == 0.0 {
+            ant.health = 0;
+            stats.starved_ants += 1;
+            events.push(SimulationEvent::AntStarved {
+                ant: entity.id(),
+                position: (*pos).into(),
+            });
+        }
+    }
+    touched
+}
+
+/// Feeds hungry ants that have reached the nest from its store, then sends wandering
+/// ants that are running low back to eat while the store has food in it.
+pub fn ant_feeding_system(
+    world: &mut World,
+    config: &SimulationConfig,
+    stats: &mut Stats,
+    events: &mut Events,
+) -> Result<u32, SimulationError> {
+    let hunger = &config.hunger;
+    let arrival_distance_sq = config.ant.arrival_distance.powi(2);
+
+    let (nest_entity, nest_pos, mut food_store) = world
+        .query::<(&Position, &Nest)>()
+        .iter()
+        .next()
+        .map(|(entity, (pos, nest))| (entity, *pos, nest.food_store))
+        .ok_or(SimulationError::MissingNest)?;
+
+    // Ants that reached the nest eat their fill, as far as the store allows
+    let mut fed = Vec::new();
+    for (entity, (pos, state, satiety)) in
+        world.query_mut::<(&Position, &mut AntState, &mut Satiety)>()
+    {
+        if *state != AntState::Hungry
+            || target_distance_sq(pos.x, pos.y, nest_pos.x, nest_pos.y) >= arrival_distance_sq
+        {
+            continue;
+        }
+
+        let wanted = ((hunger.max_satiety - satiety.0) / hunger.satiety_per_food).ceil() as u32;
+        let amount = wanted.min(food_store);
+        food_store -= amount;
+        satiety.0 = (satiety.0 + amount as f32 * hunger.satiety_per_food).min(hunger.max_satiety);
+        *state = AntState::Wandering;
+        fed.push((entity, *pos, amount));
+    }
+    let mut touched = fed.len() as u32;
+
+    for (entity, pos, amount) in fed {
+        world.remove_one::<Target>(entity).ok();
+        if amount > 0 {
+            stats.meals_eaten += 1;
+            events.push(SimulationEvent::AntAte {
+                ant: entity.id(),
+                amount,
+                position: pos.into(),
+            });
+        }
+    }
+
+    if let Ok(mut nest) = world.get::<&mut Nest>(nest_entity) {
+        nest.food_store = food_store;
+    }
+
+    // There is no point going home to an empty store
+    if food_store == 0 {
+        return Ok(touched);
+    }
+
+    let hungry_ants: Vec<_> = world
+        .query::<(&AntState, &Satiety)>()
+        .iter()
+        .filter(|(_, (state, satiety))| {
+            matches!(
+                state,
+                AntState::Wandering | AntState::ReturningToFood | AntState::Resting
+            ) && satiety.0 < hunger.hungry_threshold
+        })
+        .map(|(entity, _)| entity)
+        .collect();
+    touched += hungry_ants.len() as u32;
+
+    for entity in hungry_ants {
+        world
+            .insert(entity, (AntState::Hungry, Target(nest_entity)))
+            .map_err(|_| SimulationError::MissingEntity(entity.id()))?;
+    }
+
+    Ok(touched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hunger_system_starves_ants_that_run_out() {
+        // 1. Setup
+        let mut world = World::new();
+        let config = SimulationConfig::default();
+        let fed = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Satiety(50.0),
+        ));
+        let starving = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Satiety(0.01),
+        ));
+        let mut stats = Stats::default();
+        let mut events = Events::default();
+
+        // 2. Action
+        hunger_system(&mut world, &config, &mut stats, &mut events);
+
+        // 3. Assertion
+        assert!(world.get::<&Satiety>(fed).unwrap().0 < 50.0);
+        assert_eq!(world.get::<&Ant>(fed).unwrap().health, 100);
+        assert_eq!(world.get::<&Ant>(starving).unwrap().health, 0);
+        assert_eq!(stats.starved_ants, 1);
+        assert!(matches!(
+            events.drain()[..],
+            [SimulationEvent::AntStarved { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_hunger_system_drains_by_distance_actually_walked() {
+        // 1. Setup
+        let mut world = World::new();
+        let mut config = SimulationConfig::default();
+        config.hunger.walking_drain = 1.0;
+        config.hunger.carrying_drain = 0.0;
+        let ant = Ant {
+            health: 100,
+            birth_tick: 0,
+        };
+        let walking = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            Velocity { dx: 1.0, dy: 0.0 },
+            AntState::Wandering,
+            ant.clone(),
+            Satiety(50.0),
+        ));
+        let exhausted = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            Velocity { dx: 1.0, dy: 0.0 },
+            AntState::Wandering,
+            ant.clone(),
+            Satiety(50.0),
+            Stamina(0.0),
+        ));
+        let held = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::ReturningToFood,
+            ant,
+            Satiety(50.0),
+        ));
+
+        // 2. Action
+        hunger_system(
+            &mut world,
+            &config,
+            &mut Stats::default(),
+            &mut Events::default(),
+        );
+
+        // 3. Assertion
+        let base = config.hunger.base_drain;
+        let step = config.caste_traits(Caste::Worker).speed * config.world.seconds_per_tick;
+        let drained = |entity| 50.0 - world.get::<&Satiety>(entity).unwrap().0;
+        assert!((drained(walking) - (base + step)).abs() < 1e-4);
+        assert!((drained(exhausted) - (base + step * config.fatigue.exhausted_speed)).abs() < 1e-4);
+        assert!((drained(held) - base).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_ant_feeding_system_sends_hungry_ants_home_to_eat() {
+        // 1. Setup
+        let mut world = World::new();
+        let config = SimulationConfig::default();
+        let nest = world.spawn((Position { x: 0.0, y: 0.0 }, Nest { food_store: 10 }));
+        let ant = world.spawn((
+            Position { x: 50.0, y: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Satiety(10.0),
+        ));
+        let mut stats = Stats::default();
+        let mut events = Events::default();
+
+        // 2. Action
+        ant_feeding_system(&mut world, &config, &mut stats, &mut events).unwrap();
+        let state_when_hungry = *world.get::<&AntState>(ant).unwrap();
+        let target_when_hungry = world.get::<&Target>(ant).unwrap().0;
+        *world.get::<&mut Position>(ant).unwrap() = Position { x: 1.0, y: 0.0 };
+        ant_feeding_system(&mut world, &config, &mut stats, &mut events).unwrap();
+
+        // 3. Assertion
+        assert_eq!(state_when_hungry, AntState::Hungry);
+        assert_eq!(target_when_hungry, nest);
+        assert_eq!(*world.get::<&AntState>(ant).unwrap(), AntState::Wandering);
+        assert!(world.get::<&Target>(ant).is_err());
+        assert_eq!(
+            world.get::<&Satiety>(ant).unwrap().0,
+            config.hunger.max_satiety
+        );
+        assert_eq!(world.get::<&Nest>(nest).unwrap().food_store, 7);
+        assert_eq!(stats.meals_eaten, 1);
+    }
+
+    #[test]
+    fn test_ant_feeding_system_ignores_hunger_when_store_is_empty() {
+        // 1. Setup
+        let mut world = World::new();
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let ant = world.spawn((
+            Position { x: 50.0, y: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Satiety(10.0),
+        ));
+
+        // 2. Action
+        ant_feeding_system(
+            &mut world,
+            &SimulationConfig::default(),
+            &mut Stats::default(),
+            &mut Events::default(),
+        )
+        .unwrap();
+
+        // 3. Assertion
+        assert_eq!(*world.get::<&AntState>(ant).unwrap(), AntState::Wandering);
+    }
+}
@@ -1,11 +1,25 @@
+//! Each system returns how many entities it worked on, which the profile reports.
+
 pub mod ant;
+pub mod colony;
+pub mod fatigue;
+pub mod hunger;
 pub mod movement;
+pub mod navigation;
 pub mod pheromone;
+pub mod recruitment;
 pub mod stats;
+pub mod trophallaxis;
 pub mod world;
 
 pub use ant::*;
+pub use colony::*;
+pub use fatigue::*;
+pub use hunger::*;
 pub use movement::*;
+pub use navigation::*;
 pub use pheromone::*;
+pub use recruitment::*;
 pub use stats::*;
+pub use trophallaxis::*;
 pub use world::*;
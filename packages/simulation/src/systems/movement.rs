@@ -1,15 +1,21 @@
 use crate::components::world::{
-    Ant, AntState, FoodSource, PheromoneDeposit, PheromoneToFood, Position, Target, Velocity,
+    Ant, AntState, Caste, FoodSource, Nest, PheromoneDeposit, PheromoneToFood, Position, Role,
+    RouteMemory, Stamina, Target, Velocity,
 };
-use crate::engine::config::SIM_CONFIG;
+use crate::engine::config::SimulationConfig;
+use crate::systems::ant::is_looking_for_food;
+use crate::systems::fatigue::is_exhausted;
 use crate::utils::maths::{calculate_attraction_strength, normalise_vector, target_distance_sq};
-use hecs::World;
+use hecs::{Entity, World};
 use rand::Rng;
+use std::collections::HashMap;
 
-pub fn target_movement_system(world: &mut World) {
+pub fn target_movement_system(world: &mut World) -> u32 {
     let mut updates = Vec::new();
+    let mut touched = 0;
 
     for (entity, (pos, target)) in world.query::<(&Position, &Target)>().iter() {
+        touched += 1;
         if let Ok(target_pos) = world.get::<&Position>(target.0) {
             let dir_x = target_pos.x - pos.x;
             let dir_y = target_pos.y - pos.y;
@@ -25,18 +31,133 @@ pub fn target_movement_system(world: &mut World) {
             vel.dy = dy;
         }
     }
+    touched
 }
 
-pub fn apply_velocity_system(world: &mut World) {
-    let speed = SIM_CONFIG.movement.speed;
-    for (_entity, (pos, vel)) in world.query_mut::<(&mut Position, &Velocity)>() {
-        pos.x += vel.dx * speed;
-        pos.y += vel.dy * speed;
+/// Moves entities along their velocity for one tick, as far as `tick_displacement`
+/// says. Moves longer than an entity's discovery radius are split into sub-steps no
+/// longer than it, and an ant stops part way once it reaches its target or comes within
+/// sight of the food or nest it is looking for, so fast ants cannot skip past either.
+/// Entities heading for a target stop on it instead of overshooting.
+pub fn apply_velocity_system(world: &mut World, config: &SimulationConfig) -> u32 {
+    let target_positions: HashMap<Entity, Position> = world
+        .query::<&Target>()
+        .iter()
+        .filter_map(|(entity, target)| {
+            world
+                .get::<&Position>(target.0)
+                .ok()
+                .map(|target_pos| (entity, *target_pos))
+        })
+        .collect();
+    let food_positions: Vec<Position> = world
+        .query::<(&Position, &FoodSource)>()
+        .iter()
+        .map(|(_, (pos, _))| *pos)
+        .collect();
+    let nest_pos = world
+        .query::<(&Position, &Nest)>()
+        .iter()
+        .next()
+        .map(|(_, (pos, _))| *pos);
+    let arrival_distance_sq = config.ant.arrival_distance.powi(2);
+
+    let mut touched = 0;
+    for (entity, (pos, vel, caste, stamina, state, role)) in world.query_mut::<(
+        &mut Position,
+        &Velocity,
+        Option<&Caste>,
+        Option<&Stamina>,
+        Option<&AntState>,
+        Option<&Role>,
+    )>() {
+        touched += 1;
+        let (dx, dy) = tick_displacement(config, vel, caste, stamina);
+        let reach = caste.map_or(config.ant.discovery_radius, |caste| {
+            config.caste_traits(*caste).discovery_radius
+        });
+        let length = (dx * dx + dy * dy).sqrt();
+        let sub_steps = if reach > 0.0 {
+            (length / reach).ceil().max(1.0) as u32
+        } else {
+            1
+        };
+        let target_pos = target_positions.get(&entity);
+        let seeks_food = state.is_some_and(|state| is_looking_for_food(state, caste, role));
+        let heads_home = matches!(state, Some(AntState::ReturningToNest | AntState::Hungry));
+        let in_sight = |pos: &Position, other: &Position| {
+            target_distance_sq(pos.x, pos.y, other.x, other.y) < reach.powi(2)
+        };
+
+        let start = *pos;
+        let sub_step_sq = (dx * dx + dy * dy) / (sub_steps * sub_steps) as f32;
+        for sub_step in 1..=sub_steps {
+            if let Some(target_pos) = target_pos
+                && sub_step_sq >= target_distance_sq(pos.x, pos.y, target_pos.x, target_pos.y)
+            {
+                *pos = *target_pos;
+                break;
+            }
+            // Measured from the start so the sub-steps add up to exactly one full step
+            let fraction = sub_step as f32 / sub_steps as f32;
+            pos.x = start.x + dx * fraction;
+            pos.y = start.y + dy * fraction;
+
+            if sub_step == sub_steps {
+                break;
+            }
+            let reached_target = target_pos.is_some_and(|target_pos| {
+                target_distance_sq(pos.x, pos.y, target_pos.x, target_pos.y) < arrival_distance_sq
+            });
+            if reached_target
+                || (seeks_food && food_positions.iter().any(|food| in_sight(pos, food)))
+                || (heads_home && nest_pos.is_some_and(|nest| in_sight(pos, &nest)))
+            {
+                break;
+            }
+        }
     }
+    touched
+}
+
+/// How far an entity moves along its velocity in one tick: its caste's speed, slowed
+/// down while exhausted.
+pub(crate) fn tick_displacement(
+    config: &SimulationConfig,
+    vel: &Velocity,
+    caste: Option<&Caste>,
+    stamina: Option<&Stamina>,
+) -> (f32, f32) {
+    let speed = caste.map_or(config.movement.speed, |caste| {
+        config.caste_traits(*caste).speed
+    });
+    let mut step = speed * config.world.seconds_per_tick;
+    if is_exhausted(stamina, config) {
+        step *= config.fatigue.exhausted_speed;
+    }
+    (vel.dx * step, vel.dy * step)
+}
+
+/// How far an ant walks in a tick with its current velocity, used to drain satiety and
+/// stamina. Ants without a velocity or standing still walk nowhere.
+pub(crate) fn distance_walked(
+    config: &SimulationConfig,
+    vel: Option<&Velocity>,
+    caste: Option<&Caste>,
+    stamina: Option<&Stamina>,
+) -> f32 {
+    vel.map_or(0.0, |vel| {
+        let (dx, dy) = tick_displacement(config, vel, caste, stamina);
+        (dx * dx + dy * dy).sqrt()
+    })
 }
 
-fn set_ant_wandering(ant_vel: &mut Velocity, rng: &mut impl Rng) {
-    if rng.random_bool(SIM_CONFIG.movement.wander_probability) {
+pub(crate) fn set_ant_wandering(
+    ant_vel: &mut Velocity,
+    wander_probability: f64,
+    rng: &mut impl Rng,
+) {
+    if rng.random_bool(wander_probability) {
         let new_dx: f32 = rng.random_range(-1.0..1.0);
         let new_dy: f32 = rng.random_range(-1.0..1.0);
         if let Some((dx, dy)) = normalise_vector(new_dx, new_dy) {
@@ -46,7 +167,11 @@ fn set_ant_wandering(ant_vel: &mut Velocity, rng: &mut impl Rng) {
     }
 }
 
-fn steer_ant_towards_position(ant_pos: Position, target_pos: Position, vel: &mut Velocity) {
+pub(crate) fn steer_ant_towards_position(
+    ant_pos: Position,
+    target_pos: Position,
+    vel: &mut Velocity,
+) {
     let dir_x = target_pos.x - ant_pos.x;
     let dir_y = target_pos.y - ant_pos.y;
     if let Some((dx, dy)) = normalise_vector(dir_x, dir_y) {
@@ -72,8 +197,12 @@ fn find_nearest_food_source(ant_pos: Position, food_sources: &[Position]) -> Opt
     nearest_food.map(|(pos, _)| pos)
 }
 
-pub fn pheromone_following_system(world: &mut World, rng: &mut impl Rng) {
-    let pheromone_detection_radius_sq = SIM_CONFIG.pheromone.detection_radius.powi(2);
+pub fn pheromone_following_system(
+    world: &mut World,
+    config: &SimulationConfig,
+    rng: &mut impl Rng,
+) -> u32 {
+    let pheromone_detection_radius_sq = config.pheromone.detection_radius.powi(2);
 
     // Get all food sources
     let food_sources: Vec<Position> = world
@@ -89,10 +218,25 @@ pub fn pheromone_following_system(world: &mut World, rng: &mut impl Rng) {
         .map(|(_, (pos, deposit, _))| (*pos, deposit.strength))
         .collect();
 
-    for (_entity, (pos, vel, state, _)) in
-        world.query_mut::<(&Position, &mut Velocity, &AntState, &Ant)>()
-    {
-        if *state == AntState::Wandering {
+    let mut touched = 0;
+    for (_entity, (pos, vel, state, _, caste, role)) in world.query_mut::<(
+        &Position,
+        &mut Velocity,
+        &AntState,
+        &Ant,
+        Option<&Caste>,
+        Option<&Role>,
+    )>() {
+        touched += 1;
+        let caste = caste.copied().unwrap_or_default();
+        let wander_probability = config.caste_traits(caste).wander_probability;
+
+        // Only foraging workers follow trails; nurses, scouts and soldiers keep roaming
+        if *state == AntState::Wandering
+            && (caste != Caste::Worker || role.copied() == Some(Role::Nurse))
+        {
+            set_ant_wandering(vel, wander_probability, rng);
+        } else if *state == AntState::Wandering {
             let mut best_pheromone: Option<(Position, f32)> = None;
 
             // Find the strongest and closest pheromone
@@ -117,13 +261,132 @@ pub fn pheromone_following_system(world: &mut World, rng: &mut impl Rng) {
                 if let Some(food_pos) = nearest_food {
                     steer_ant_towards_position(*pos, food_pos, vel);
                 } else {
-                    set_ant_wandering(vel, rng);
+                    set_ant_wandering(vel, wander_probability, rng);
                 }
             } else {
-                set_ant_wandering(vel, rng);
+                set_ant_wandering(vel, wander_probability, rng);
+            }
+        }
+    }
+    touched
+}
+
+/// Turns wandering soldiers that stray beyond the patrol radius back towards the nest.
+pub fn soldier_patrol_system(world: &mut World, config: &SimulationConfig) -> u32 {
+    let Some(nest_pos) = world
+        .query::<(&Position, &Nest)>()
+        .iter()
+        .next()
+        .map(|(_, (pos, _))| *pos)
+    else {
+        return 0;
+    };
+    let patrol_radius_sq = config.castes.soldier_patrol_radius.powi(2);
+
+    let mut touched = 0;
+    for (_entity, (pos, vel, state, caste)) in
+        world.query_mut::<(&Position, &mut Velocity, &AntState, &Caste)>()
+    {
+        touched += 1;
+        if *caste == Caste::Soldier
+            && *state == AntState::Wandering
+            && target_distance_sq(pos.x, pos.y, nest_pos.x, nest_pos.y) > patrol_radius_sq
+        {
+            steer_ant_towards_position(*pos, nest_pos, vel);
+        }
+    }
+    touched
+}
+
+/// Keeps wandering nurses within `nurse_radius` of the nest. This is all the nurse role
+/// does; it has no effect on how brood develops.
+pub fn nurse_system(world: &mut World, config: &SimulationConfig) -> u32 {
+    let Some(nest_pos) = world
+        .query::<(&Position, &Nest)>()
+        .iter()
+        .next()
+        .map(|(_, (pos, _))| *pos)
+    else {
+        return 0;
+    };
+    let nurse_radius_sq = config.roles.nurse_radius.powi(2);
+
+    let mut touched = 0;
+    for (_entity, (pos, vel, state, role)) in
+        world.query_mut::<(&Position, &mut Velocity, &AntState, &Role)>()
+    {
+        touched += 1;
+        if *role == Role::Nurse
+            && *state == AntState::Wandering
+            && target_distance_sq(pos.x, pos.y, nest_pos.x, nest_pos.y) > nurse_radius_sq
+        {
+            steer_ant_towards_position(*pos, nest_pos, vel);
+        }
+    }
+    touched
+}
+
+/// Steers ants back to the food they remember. An ant that arrives to find no food
+/// within its discovery radius forgets the spot at once. Otherwise it searches the area,
+/// setting off again once it has strayed too far, and forgets the spot on arriving after
+/// `max_failed_visits` visits without picking anything up.
+pub fn route_memory_system(world: &mut World, config: &SimulationConfig) -> u32 {
+    let arrival_distance_sq = config.ant.arrival_distance.powi(2);
+    let max_failed_visits = config.ant.max_failed_visits;
+    let food_positions: Vec<Position> = world
+        .query::<(&Position, &FoodSource)>()
+        .iter()
+        .filter(|(_, (_, food))| food.amount > 0)
+        .map(|(_, (pos, _))| *pos)
+        .collect();
+    let mut forgotten = Vec::new();
+    let mut touched = 0;
+
+    for (entity, (pos, vel, state, memory, caste)) in world.query_mut::<(
+        &Position,
+        &mut Velocity,
+        &mut AntState,
+        &mut RouteMemory,
+        Option<&Caste>,
+    )>() {
+        touched += 1;
+        let discovery_radius = config
+            .caste_traits(caste.copied().unwrap_or_default())
+            .discovery_radius;
+        let distance_sq = target_distance_sq(pos.x, pos.y, memory.position.x, memory.position.y);
+        match *state {
+            AntState::ReturningToFood if distance_sq < arrival_distance_sq => {
+                *state = AntState::Wandering;
+                let food_left = food_positions.iter().any(|food| {
+                    target_distance_sq(food.x, food.y, memory.position.x, memory.position.y)
+                        < discovery_radius.powi(2)
+                });
+                if !food_left || memory.failed_visits >= max_failed_visits {
+                    forgotten.push(entity);
+                } else {
+                    // Picking up food here resets the count, so only visits that end
+                    // empty-handed add up
+                    memory.failed_visits += 1;
+                }
+            }
+            AntState::ReturningToFood => {
+                steer_ant_towards_position(*pos, memory.position, vel);
+            }
+            AntState::Wandering => {
+                let search_radius = discovery_radius * 2.0;
+                if distance_sq > search_radius.powi(2) {
+                    *state = AntState::ReturningToFood;
+                    steer_ant_towards_position(*pos, memory.position, vel);
+                }
             }
+            _ => {}
         }
     }
+
+    for entity in forgotten {
+        world.remove_one::<RouteMemory>(entity).ok();
+    }
+    touched
 }
 
 #[cfg(test)]
@@ -132,6 +395,8 @@ mod tests {
     use crate::components::world::{
         FoodSource, PheromoneDeposit, PheromoneToFood, Position, Target, Velocity,
     };
+    use crate::engine::events::Events;
+    use crate::systems::ant::ant_find_food_system;
     use hecs::World;
     use rand::SeedableRng;
     use rand::rngs::StdRng;
@@ -140,7 +405,13 @@ mod tests {
     fn test_target_movement_system_moves_towards_target() {
         // 1. Setup
         let mut world = World::new();
-        let food_entity = world.spawn((Position { x: 10.0, y: 10.0 }, FoodSource { amount: 100 }));
+        let food_entity = world.spawn((
+            Position { x: 10.0, y: 10.0 },
+            FoodSource {
+                amount: 100,
+                birth_tick: 0,
+            },
+        ));
         let ant_entity = world.spawn((
             Position { x: 0.0, y: 0.0 },
             Velocity { dx: 0.0, dy: 0.0 },
@@ -169,7 +440,7 @@ mod tests {
         ));
 
         // 2. Action
-        apply_velocity_system(&mut world);
+        apply_velocity_system(&mut world, &SimulationConfig::default());
 
         // 3. Assertion
         let pos = world.get::<&Position>(entity).unwrap();
@@ -177,6 +448,81 @@ mod tests {
         assert_eq!(pos.y, 5.5);
     }
 
+    #[test]
+    fn test_apply_velocity_system_stops_on_target_at_high_speed() {
+        // 1. Setup
+        let mut world = World::new();
+        let mut config = SimulationConfig::default();
+        config.movement.speed = 1000.0;
+        let food_entity = world.spawn((
+            Position { x: 20.0, y: 0.0 },
+            FoodSource {
+                amount: 100,
+                birth_tick: 0,
+            },
+        ));
+        let ant_entity = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            Velocity { dx: 1.0, dy: 0.0 },
+            Target(food_entity),
+        ));
+
+        // 2. Action
+        apply_velocity_system(&mut world, &config);
+
+        // 3. Assertion
+        let pos = world.get::<&Position>(ant_entity).unwrap();
+        assert_eq!(*pos, Position { x: 20.0, y: 0.0 });
+    }
+
+    #[test]
+    fn test_apply_velocity_system_covers_full_step_without_skipping_food() {
+        // 1. Setup
+        let mut world = World::new();
+        let mut config = SimulationConfig::default();
+        config.castes.worker.speed = Some(1000.0);
+        config.castes.worker.discovery_radius = Some(5.0);
+        world.spawn((
+            Position { x: 50.0, y: 3.0 },
+            FoodSource {
+                amount: 100,
+                birth_tick: 0,
+            },
+        ));
+        let spawn_ant = |world: &mut World, y: f32| {
+            world.spawn((
+                Position { x: 0.0, y },
+                Velocity { dx: 1.0, dy: 0.0 },
+                AntState::Wandering,
+                Ant {
+                    health: 100,
+                    birth_tick: 0,
+                },
+                Caste::Worker,
+            ))
+        };
+        let passing_food = spawn_ant(&mut world, 0.0);
+        let clear_path = spawn_ant(&mut world, 100.0);
+
+        // 2. Action
+        apply_velocity_system(&mut world, &config);
+        ant_find_food_system(&mut world, &config, &mut Events::default()).unwrap();
+
+        // 3. Assertion
+        assert_eq!(
+            *world.get::<&Position>(clear_path).unwrap(),
+            Position { x: 100.0, y: 100.0 }
+        );
+        assert_eq!(
+            *world.get::<&Position>(passing_food).unwrap(),
+            Position { x: 50.0, y: 0.0 }
+        );
+        assert_eq!(
+            *world.get::<&AntState>(passing_food).unwrap(),
+            AntState::Foraging
+        );
+    }
+
     #[test]
     fn test_pheromone_following_system_no_pheromones_fallback_to_wandering() {
         let mut world = World::new();
@@ -186,11 +532,14 @@ mod tests {
             Position { x: 0.0, y: 0.0 },
             Velocity { dx: 0.0, dy: 0.0 },
             AntState::Wandering,
-            Ant { health: 100 },
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
         ));
 
         for _ in 0..100 {
-            pheromone_following_system(&mut world, &mut rng);
+            pheromone_following_system(&mut world, &SimulationConfig::default(), &mut rng);
         }
 
         let vel = world.get::<&Velocity>(ant_entity).unwrap();
@@ -208,11 +557,20 @@ mod tests {
             Position { x: 9.9, y: 0.0 },
             Velocity { dx: 0.0, dy: 0.0 },
             AntState::Wandering,
-            Ant { health: 100 },
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
         ));
 
         // Food source further away
-        world.spawn((Position { x: 20.0, y: 0.0 }, FoodSource { amount: 100 }));
+        world.spawn((
+            Position { x: 20.0, y: 0.0 },
+            FoodSource {
+                amount: 100,
+                birth_tick: 0,
+            },
+        ));
 
         // Pheromone very close to ant
         world.spawn((
@@ -222,7 +580,7 @@ mod tests {
         ));
 
         for _ in 0..10 {
-            pheromone_following_system(&mut world, &mut rng);
+            pheromone_following_system(&mut world, &SimulationConfig::default(), &mut rng);
         }
 
         let vel = world.get::<&Velocity>(ant_entity).unwrap();
@@ -230,4 +588,134 @@ mod tests {
         assert!(vel.dx > 0.0);
         assert_eq!(vel.dy, 0.0);
     }
+
+    #[test]
+    fn test_soldier_patrol_system_turns_soldiers_back_to_nest() {
+        // 1. Setup
+        let mut world = World::new();
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let soldier = world.spawn((
+            Position { x: 50.0, y: 0.0 },
+            Velocity { dx: 1.0, dy: 0.0 },
+            AntState::Wandering,
+            Caste::Soldier,
+        ));
+        let worker = world.spawn((
+            Position { x: 50.0, y: 0.0 },
+            Velocity { dx: 1.0, dy: 0.0 },
+            AntState::Wandering,
+            Caste::Worker,
+        ));
+
+        // 2. Action
+        soldier_patrol_system(&mut world, &SimulationConfig::default());
+
+        // 3. Assertion
+        let soldier_vel = world.get::<&Velocity>(soldier).unwrap();
+        assert_eq!((soldier_vel.dx, soldier_vel.dy), (-1.0, 0.0));
+        let worker_vel = world.get::<&Velocity>(worker).unwrap();
+        assert_eq!((worker_vel.dx, worker_vel.dy), (1.0, 0.0));
+    }
+
+    #[test]
+    fn test_nurse_system_keeps_nurses_near_nest() {
+        // 1. Setup
+        let mut world = World::new();
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let nurse = world.spawn((
+            Position { x: 0.0, y: 30.0 },
+            Velocity { dx: 0.0, dy: 1.0 },
+            AntState::Wandering,
+            Role::Nurse,
+        ));
+        let forager = world.spawn((
+            Position { x: 0.0, y: 30.0 },
+            Velocity { dx: 0.0, dy: 1.0 },
+            AntState::Wandering,
+            Role::Forager,
+        ));
+
+        // 2. Action
+        nurse_system(&mut world, &SimulationConfig::default());
+
+        // 3. Assertion
+        let nurse_vel = world.get::<&Velocity>(nurse).unwrap();
+        assert_eq!((nurse_vel.dx, nurse_vel.dy), (0.0, -1.0));
+        let forager_vel = world.get::<&Velocity>(forager).unwrap();
+        assert_eq!((forager_vel.dx, forager_vel.dy), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_route_memory_system_forgets_source_that_is_gone() {
+        // 1. Setup
+        let mut world = World::new();
+        let config = SimulationConfig::default();
+        let food_pos = Position { x: 100.0, y: 0.0 };
+        let ant_entity = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 1.0 },
+            AntState::ReturningToFood,
+            RouteMemory {
+                position: food_pos,
+                failed_visits: 0,
+            },
+        ));
+
+        // 2. Action
+        route_memory_system(&mut world, &config);
+        let heading = (*world.get::<&Velocity>(ant_entity).unwrap()).clone();
+        *world.get::<&mut Position>(ant_entity).unwrap() = food_pos;
+        route_memory_system(&mut world, &config);
+
+        // 3. Assertion
+        assert_eq!((heading.dx, heading.dy), (1.0, 0.0));
+        assert!(world.get::<&RouteMemory>(ant_entity).is_err());
+        assert_eq!(
+            *world.get::<&AntState>(ant_entity).unwrap(),
+            AntState::Wandering
+        );
+    }
+
+    #[test]
+    fn test_route_memory_system_forgets_after_failed_visits() {
+        // 1. Setup
+        let mut world = World::new();
+        let mut config = SimulationConfig::default();
+        config.ant.max_failed_visits = 2;
+        let food_pos = Position { x: 100.0, y: 0.0 };
+        world.spawn((
+            food_pos,
+            FoodSource {
+                amount: 10,
+                birth_tick: 0,
+            },
+        ));
+        let ant_entity = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 1.0 },
+            AntState::ReturningToFood,
+            RouteMemory {
+                position: food_pos,
+                failed_visits: 0,
+            },
+        ));
+
+        // 2. Action
+        let mut remembered = Vec::new();
+        for _ in 0..3 {
+            // Arrive at the remembered spot, then stray away from it again
+            *world.get::<&mut Position>(ant_entity).unwrap() = food_pos;
+            route_memory_system(&mut world, &config);
+            remembered.push(world.get::<&RouteMemory>(ant_entity).is_ok());
+            *world.get::<&mut Position>(ant_entity).unwrap() = Position { x: 0.0, y: 0.0 };
+            route_memory_system(&mut world, &config);
+        }
+
+        // 3. Assertion
+        assert_eq!(remembered, vec![true, true, false]);
+        assert_eq!(
+            *world.get::<&AntState>(ant_entity).unwrap(),
+            AntState::Wandering
+        );
+    }
 }
@@ -0,0 +1,245 @@
+use crate::components::world::{Ant, AntState, Caste, HomingVector, Nest, Position, Velocity};
+use crate::engine::config::SimulationConfig;
+use crate::systems::movement::{set_ant_wandering, steer_ant_towards_position};
+use crate::utils::maths::target_distance_sq;
+use hecs::World;
+use rand::Rng;
+
+fn nest_position(world: &World) -> Option<Position> {
+    world
+        .query::<(&Position, &Nest)>()
+        .iter()
+        .next()
+        .map(|(_, (pos, _))| *pos)
+}
+
+/// Adds each ant's latest movement to its homing vector, with a random error in the
+/// direction and length of every step. The estimate is corrected whenever the ant is
+/// back at the nest. Ants without a homing vector start with an exact one.
+pub fn path_integration_system(
+    world: &mut World,
+    config: &SimulationConfig,
+    rng: &mut impl Rng,
+) -> u32 {
+    let navigation = &config.navigation;
+    if !navigation.path_integration {
+        return 0;
+    }
+    let Some(nest_pos) = nest_position(world) else {
+        return 0;
+    };
+    let arrival_distance_sq = config.ant.arrival_distance.powi(2);
+
+    let untracked: Vec<_> = world
+        .query::<&Position>()
+        .with::<&Ant>()
+        .without::<&HomingVector>()
+        .iter()
+        .map(|(entity, pos)| (entity, *pos))
+        .collect();
+    for (entity, pos) in untracked {
+        world
+            .insert_one(
+                entity,
+                HomingVector {
+                    dx: pos.x - nest_pos.x,
+                    dy: pos.y - nest_pos.y,
+                    last_position: pos,
+                },
+            )
+            .ok();
+    }
+
+    let mut touched = 0;
+    for (_entity, (pos, homing)) in world.query_mut::<(&Position, &mut HomingVector)>() {
+        touched += 1;
+        if target_distance_sq(pos.x, pos.y, nest_pos.x, nest_pos.y) < arrival_distance_sq {
+            homing.dx = pos.x - nest_pos.x;
+            homing.dy = pos.y - nest_pos.y;
+            homing.last_position = *pos;
+            continue;
+        }
+
+        let step_x = pos.x - homing.last_position.x;
+        let step_y = pos.y - homing.last_position.y;
+        let angle = rng.random_range(-navigation.angular_error..=navigation.angular_error);
+        let scale = 1.0 + rng.random_range(-navigation.distance_error..=navigation.distance_error);
+        let (sin, cos) = angle.sin_cos();
+        homing.dx += (step_x * cos - step_y * sin) * scale;
+        homing.dy += (step_x * sin + step_y * cos) * scale;
+        homing.last_position = *pos;
+    }
+    touched
+}
+
+/// Steers homeward ants by their homing vector rather than straight at the nest. Once
+/// they reach where they think the nest is they search around it, heading for the real
+/// nest as soon as it is within their discovery radius.
+pub fn homing_system(world: &mut World, config: &SimulationConfig, rng: &mut impl Rng) -> u32 {
+    if !config.navigation.path_integration {
+        return 0;
+    }
+    let Some(nest_pos) = nest_position(world) else {
+        return 0;
+    };
+    let search_radius_sq = config.navigation.search_radius.powi(2);
+    let mut touched = 0;
+
+    for (_entity, (pos, vel, state, homing, caste)) in world.query_mut::<(
+        &Position,
+        &mut Velocity,
+        &AntState,
+        &HomingVector,
+        Option<&Caste>,
+    )>() {
+        if !matches!(state, AntState::ReturningToNest | AntState::Hungry) {
+            continue;
+        }
+        touched += 1;
+
+        let traits = config.caste_traits(caste.copied().unwrap_or_default());
+        let believed_nest = Position {
+            x: pos.x - homing.dx,
+            y: pos.y - homing.dy,
+        };
+
+        if target_distance_sq(pos.x, pos.y, nest_pos.x, nest_pos.y)
+            < traits.discovery_radius.powi(2)
+        {
+            steer_ant_towards_position(*pos, nest_pos, vel);
+        } else if target_distance_sq(pos.x, pos.y, believed_nest.x, believed_nest.y)
+            > search_radius_sq
+        {
+            steer_ant_towards_position(*pos, believed_nest, vel);
+        } else {
+            set_ant_wandering(vel, traits.wander_probability, rng);
+        }
+    }
+    touched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::world::Target;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn navigation_config(angular_error: f32, distance_error: f32) -> SimulationConfig {
+        let mut config = SimulationConfig::default();
+        config.navigation.path_integration = true;
+        config.navigation.angular_error = angular_error;
+        config.navigation.distance_error = distance_error;
+        config
+    }
+
+    #[test]
+    fn test_path_integration_system_tracks_displacement_without_error() {
+        // 1. Setup
+        let mut world = World::new();
+        let mut rng = StdRng::seed_from_u64(42);
+        let config = navigation_config(0.0, 0.0);
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let ant = world.spawn((
+            Position { x: 20.0, y: 0.0 },
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+        ));
+
+        // 2. Action
+        path_integration_system(&mut world, &config, &mut rng);
+        *world.get::<&mut Position>(ant).unwrap() = Position { x: 30.0, y: 40.0 };
+        path_integration_system(&mut world, &config, &mut rng);
+
+        // 3. Assertion
+        let homing = *world.get::<&HomingVector>(ant).unwrap();
+        assert_eq!((homing.dx, homing.dy), (30.0, 40.0));
+    }
+
+    #[test]
+    fn test_path_integration_system_drifts_with_error() {
+        // 1. Setup
+        let mut world = World::new();
+        let mut rng = StdRng::seed_from_u64(42);
+        let config = navigation_config(0.3, 0.3);
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let ant = world.spawn((
+            Position { x: 20.0, y: 0.0 },
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+        ));
+
+        // 2. Action
+        path_integration_system(&mut world, &config, &mut rng);
+        for step in 1..=20 {
+            *world.get::<&mut Position>(ant).unwrap() = Position {
+                x: 20.0 + step as f32 * 5.0,
+                y: 0.0,
+            };
+            path_integration_system(&mut world, &config, &mut rng);
+        }
+
+        // 3. Assertion
+        let homing = *world.get::<&HomingVector>(ant).unwrap();
+        assert_ne!((homing.dx, homing.dy), (120.0, 0.0));
+        assert!((homing.dx - 120.0).abs() < 40.0);
+    }
+
+    #[test]
+    fn test_homing_system_steers_by_belief_not_by_nest() {
+        // 1. Setup
+        let mut world = World::new();
+        let mut rng = StdRng::seed_from_u64(42);
+        let config = navigation_config(0.0, 0.0);
+        let nest = world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        // The ant is east of the nest but believes it is north of it
+        let ant = world.spawn((
+            Position { x: 100.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::ReturningToNest,
+            Target(nest),
+            HomingVector {
+                dx: 0.0,
+                dy: 100.0,
+                last_position: Position { x: 100.0, y: 0.0 },
+            },
+        ));
+
+        // 2. Action
+        homing_system(&mut world, &config, &mut rng);
+
+        // 3. Assertion
+        let vel = world.get::<&Velocity>(ant).unwrap();
+        assert_eq!((vel.dx, vel.dy), (0.0, -1.0));
+    }
+
+    #[test]
+    fn test_homing_system_heads_for_nest_once_it_is_in_sight() {
+        // 1. Setup
+        let mut world = World::new();
+        let mut rng = StdRng::seed_from_u64(42);
+        let config = navigation_config(0.0, 0.0);
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let ant = world.spawn((
+            Position { x: 20.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::ReturningToNest,
+            HomingVector {
+                dx: 0.0,
+                dy: 0.0,
+                last_position: Position { x: 20.0, y: 0.0 },
+            },
+        ));
+
+        // 2. Action
+        homing_system(&mut world, &config, &mut rng);
+
+        // 3. Assertion
+        let vel = world.get::<&Velocity>(ant).unwrap();
+        assert_eq!((vel.dx, vel.dy), (-1.0, 0.0));
+    }
+}
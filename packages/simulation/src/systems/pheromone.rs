@@ -1,11 +1,15 @@
 use crate::components::world::{
     Ant, AntState, FoodPayload, PheromoneDeposit, PheromoneToFood, Position,
 };
-use crate::engine::config::SIM_CONFIG;
+use crate::engine::config::SimulationConfig;
 use hecs::World;
 use rand::Rng;
 
-pub fn pheromone_emission_system(world: &mut World, rng: &mut impl Rng) {
+pub fn pheromone_emission_system(
+    world: &mut World,
+    config: &SimulationConfig,
+    rng: &mut impl Rng,
+) -> u32 {
     // TODO: Determine strength of pheromones based on distance from food source / to nest
 
     // TODO: Currently only emits to_food pheromones
@@ -20,28 +24,33 @@ pub fn pheromone_emission_system(world: &mut World, rng: &mut impl Rng) {
             }
         })
         .collect();
+    let touched = ants_returning_to_nest.len() as u32;
 
     for position in ants_returning_to_nest {
-        if rng.random_bool(SIM_CONFIG.pheromone.emit_chance) {
+        if rng.random_bool(config.pheromone.emit_chance) {
             world.spawn((
                 Position {
                     x: position.x,
                     y: position.y,
                 },
                 PheromoneDeposit {
-                    strength: SIM_CONFIG.pheromone.initial_strength,
+                    strength: config.pheromone.initial_strength,
                 },
                 PheromoneToFood,
             ));
         }
     }
+    touched
 }
 
-pub fn pheromone_decay_system(world: &mut World) {
+pub fn pheromone_decay_system(world: &mut World, config: &SimulationConfig) -> u32 {
+    let mut touched = 0;
     // TODO: Different pheromones should decay at different rates
     for (_entity, (pheromone, _)) in world.query_mut::<(&mut PheromoneDeposit, &Position)>() {
-        pheromone.strength -= SIM_CONFIG.pheromone.decay_amount;
+        touched += 1;
+        pheromone.strength -= config.pheromone.decay_amount;
     }
+    touched
 }
 
 #[cfg(test)]
@@ -65,12 +74,15 @@ mod tests {
             Position { x: 36.0, y: 48.0 },
             AntState::ReturningToNest,
             FoodPayload(10),
-            Ant { health: 100 },
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
         ));
 
         // 2. Action
         for _ in 0..50 {
-            pheromone_emission_system(&mut world, &mut rng);
+            pheromone_emission_system(&mut world, &SimulationConfig::default(), &mut rng);
         }
 
         // 3. Assertion
@@ -93,7 +105,7 @@ mod tests {
         ));
 
         // 2. Action
-        pheromone_decay_system(&mut world);
+        pheromone_decay_system(&mut world, &SimulationConfig::default());
 
         // 3. Assertion
         let mut query = world.query::<(&PheromoneDeposit, &PheromoneToFood, &Position)>();
@@ -0,0 +1,456 @@
+use crate::components::world::{
+    Ant, AntState, Caste, Nest, Position, Role, RouteMemory, TandemFollower, TandemLeader, Target,
+    Velocity,
+};
+use crate::engine::config::SimulationConfig;
+use crate::engine::events::{Events, SimulationEvent};
+use crate::engine::stats::Stats;
+use crate::utils::maths::target_distance_sq;
+use hecs::{Entity, World};
+use std::collections::HashSet;
+
+/// Ends tandem runs whose leader has reached its food, given up or gone, then pairs
+/// foragers leaving the nest for a remembered source with the nearest idle nestmate.
+/// Followers that made it to the food remember the source themselves. Scouts lead runs
+/// but find their own food, so they are never recruited.
+pub fn recruitment_system(
+    world: &mut World,
+    config: &SimulationConfig,
+    stats: &mut Stats,
+    events: &mut Events,
+) -> u32 {
+    let followers: Vec<(Entity, AntState, Option<Entity>)> = world
+        .query::<(&AntState, Option<&Target>)>()
+        .with::<&TandemFollower>()
+        .iter()
+        .map(|(entity, (state, target))| (entity, *state, target.map(|t| t.0)))
+        .collect();
+    let mut touched = followers.len() as u32;
+
+    let mut active_leaders = HashSet::new();
+    for (follower, state, leader) in followers {
+        let leader_state = leader
+            .filter(|leader| world.get::<&TandemLeader>(*leader).is_ok())
+            .and_then(|leader| world.get::<&AntState>(leader).ok().map(|s| *s));
+
+        if state == AntState::Following {
+            match (leader, leader_state) {
+                (Some(leader), Some(AntState::ReturningToFood)) => {
+                    active_leaders.insert(leader);
+                    continue;
+                }
+                (Some(leader), Some(AntState::Foraging)) => {
+                    let memory = world.get::<&RouteMemory>(leader).ok().map(|m| m.position);
+                    if let Some(position) = memory {
+                        world
+                            .insert_one(
+                                follower,
+                                RouteMemory {
+                                    position,
+                                    failed_visits: 0,
+                                },
+                            )
+                            .ok();
+                    }
+                    stats.tandem_runs_completed += 1;
+                    if let Ok(pos) = world.get::<&Position>(follower) {
+                        events.push(SimulationEvent::TandemRunCompleted {
+                            leader: leader.id(),
+                            follower: follower.id(),
+                            position: (*pos).into(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+            if let Ok(state) = world.query_one_mut::<&mut AntState>(follower) {
+                *state = AntState::Wandering;
+            }
+            world.remove_one::<Target>(follower).ok();
+        }
+        world.remove_one::<TandemFollower>(follower).ok();
+    }
+
+    let finished_leaders: Vec<Entity> = world
+        .query::<&TandemLeader>()
+        .iter()
+        .map(|(entity, _)| entity)
+        .filter(|entity| !active_leaders.contains(entity))
+        .collect();
+    touched += finished_leaders.len() as u32;
+    for leader in finished_leaders {
+        world.remove_one::<TandemLeader>(leader).ok();
+    }
+
+    let recruitment = &config.recruitment;
+    if !recruitment.tandem_runs {
+        return touched;
+    }
+    let Some(nest_pos) = world
+        .query::<(&Position, &Nest)>()
+        .iter()
+        .next()
+        .map(|(_, (pos, _))| *pos)
+    else {
+        return touched;
+    };
+    let radius_sq = recruitment.recruitment_radius.powi(2);
+
+    let leaders: Vec<(Entity, Position)> = world
+        .query::<(&Position, &AntState, Option<&Caste>)>()
+        .with::<(&Ant, &RouteMemory)>()
+        .without::<&TandemLeader>()
+        .iter()
+        .filter(|(_, (pos, state, caste))| {
+            **state == AntState::ReturningToFood
+                && caste.copied().unwrap_or_default() != Caste::Soldier
+                && target_distance_sq(pos.x, pos.y, nest_pos.x, nest_pos.y) <= radius_sq
+        })
+        .map(|(entity, (pos, _, _))| (entity, *pos))
+        .collect();
+    touched += leaders.len() as u32;
+    if leaders.is_empty() {
+        return touched;
+    }
+
+    let mut idle: Vec<(Entity, Position)> = world
+        .query::<(&Position, &AntState, Option<&Caste>, Option<&Role>)>()
+        .with::<&Ant>()
+        .without::<&RouteMemory>()
+        .without::<&TandemFollower>()
+        .without::<&TandemLeader>()
+        .iter()
+        .filter(|(_, (_, state, caste, role))| {
+            **state == AntState::Wandering
+                && caste.copied().unwrap_or_default() == Caste::Worker
+                && role.copied() != Some(Role::Nurse)
+        })
+        .map(|(entity, (pos, _, _, _))| (entity, *pos))
+        .collect();
+    touched += idle.len() as u32;
+
+    for (leader, leader_pos) in leaders {
+        let nearest = idle
+            .iter()
+            .enumerate()
+            .map(|(index, (_, pos))| {
+                (
+                    index,
+                    target_distance_sq(pos.x, pos.y, leader_pos.x, leader_pos.y),
+                )
+            })
+            .filter(|(_, distance_sq)| *distance_sq <= radius_sq)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+        let Some((index, _)) = nearest else {
+            continue;
+        };
+        let (follower, _) = idle.swap_remove(index);
+
+        world.insert_one(leader, TandemLeader).ok();
+        world
+            .insert(
+                follower,
+                (TandemFollower, Target(leader), AntState::Following),
+            )
+            .ok();
+        stats.tandem_runs_started += 1;
+        events.push(SimulationEvent::TandemRunStarted {
+            leader: leader.id(),
+            follower: follower.id(),
+            position: leader_pos.into(),
+        });
+    }
+    touched
+}
+
+/// Holds tandem leaders in place while their follower is too far behind.
+pub fn tandem_run_system(world: &mut World, config: &SimulationConfig) -> u32 {
+    let max_gap_sq = config.recruitment.max_gap.powi(2);
+
+    let following: Vec<(Position, Entity)> = world
+        .query::<(&Position, &AntState, &Target)>()
+        .with::<&TandemFollower>()
+        .iter()
+        .filter(|(_, (_, state, _))| **state == AntState::Following)
+        .map(|(_, (pos, _, target))| (*pos, target.0))
+        .collect();
+    let lagging: Vec<Entity> = following
+        .iter()
+        .filter_map(|(pos, leader)| {
+            let leader_pos = world.get::<&Position>(*leader).ok()?;
+            (target_distance_sq(pos.x, pos.y, leader_pos.x, leader_pos.y) > max_gap_sq)
+                .then_some(*leader)
+        })
+        .collect();
+
+    for leader in lagging {
+        if let Ok(vel) = world.query_one_mut::<&mut Velocity>(leader) {
+            vel.dx = 0.0;
+            vel.dy = 0.0;
+        }
+    }
+    following.len() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::world::FoodPayload;
+
+    #[test]
+    fn test_recruitment_system_pairs_leader_with_nearest_idle_ant() {
+        // 1. Setup
+        let mut world = World::new();
+        let config = SimulationConfig::default();
+        let mut stats = Stats::default();
+        let mut events = Events::default();
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let leader = world.spawn((
+            Position { x: 1.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::ReturningToFood,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Caste::Worker,
+            RouteMemory {
+                position: Position { x: 50.0, y: 0.0 },
+                failed_visits: 0,
+            },
+        ));
+        let near = world.spawn((
+            Position { x: 2.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Caste::Worker,
+        ));
+        let far = world.spawn((
+            Position { x: 8.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Caste::Worker,
+        ));
+        world.spawn((
+            Position { x: 1.0, y: 1.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::ReturningToNest,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Caste::Worker,
+            FoodPayload(1),
+        ));
+
+        // 2. Action
+        recruitment_system(&mut world, &config, &mut stats, &mut events);
+
+        // 3. Assertion
+        assert!(world.get::<&TandemLeader>(leader).is_ok());
+        assert_eq!(*world.get::<&AntState>(near).unwrap(), AntState::Following);
+        assert_eq!(world.get::<&Target>(near).unwrap().0, leader);
+        assert_eq!(*world.get::<&AntState>(far).unwrap(), AntState::Wandering);
+        assert_eq!(stats.tandem_runs_started, 1);
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn test_recruitment_system_lets_scouts_lead_but_not_follow() {
+        // 1. Setup
+        let mut world = World::new();
+        let config = SimulationConfig::default();
+        let mut stats = Stats::default();
+        let mut events = Events::default();
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let scout = world.spawn((
+            Position { x: 1.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::ReturningToFood,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Caste::Scout,
+            RouteMemory {
+                position: Position { x: 50.0, y: 0.0 },
+                failed_visits: 0,
+            },
+        ));
+        let idle_scout = world.spawn((
+            Position { x: 2.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Caste::Scout,
+        ));
+        let worker = world.spawn((
+            Position { x: 4.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Caste::Worker,
+        ));
+
+        // 2. Action
+        recruitment_system(&mut world, &config, &mut stats, &mut events);
+
+        // 3. Assertion
+        assert!(world.get::<&TandemLeader>(scout).is_ok());
+        assert_eq!(
+            *world.get::<&AntState>(idle_scout).unwrap(),
+            AntState::Wandering
+        );
+        assert_eq!(world.get::<&Target>(worker).unwrap().0, scout);
+    }
+
+    #[test]
+    fn test_recruitment_system_hands_route_to_follower_at_food() {
+        // 1. Setup
+        let mut world = World::new();
+        let config = SimulationConfig::default();
+        let mut stats = Stats::default();
+        let mut events = Events::default();
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let leader = world.spawn((
+            Position { x: 1.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::ReturningToFood,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Caste::Worker,
+            RouteMemory {
+                position: Position { x: 50.0, y: 0.0 },
+                failed_visits: 0,
+            },
+        ));
+        let follower = world.spawn((
+            Position { x: 2.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Caste::Worker,
+        ));
+        recruitment_system(&mut world, &config, &mut stats, &mut events);
+        *world.get::<&mut AntState>(leader).unwrap() = AntState::Foraging;
+
+        // 2. Action
+        recruitment_system(&mut world, &config, &mut stats, &mut events);
+
+        // 3. Assertion
+        assert!(world.get::<&TandemLeader>(leader).is_err());
+        assert!(world.get::<&TandemFollower>(follower).is_err());
+        assert!(world.get::<&Target>(follower).is_err());
+        assert_eq!(
+            *world.get::<&AntState>(follower).unwrap(),
+            AntState::Wandering
+        );
+        assert_eq!(
+            world.get::<&RouteMemory>(follower).unwrap().position,
+            Position { x: 50.0, y: 0.0 }
+        );
+        assert_eq!(stats.tandem_runs_completed, 1);
+    }
+
+    #[test]
+    fn test_recruitment_system_releases_follower_when_leader_is_gone() {
+        // 1. Setup
+        let mut world = World::new();
+        let config = SimulationConfig::default();
+        let mut stats = Stats::default();
+        let mut events = Events::default();
+        world.spawn((Position { x: 0.0, y: 0.0 }, Nest::new()));
+        let leader = world.spawn((
+            Position { x: 1.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::ReturningToFood,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Caste::Worker,
+            RouteMemory {
+                position: Position { x: 50.0, y: 0.0 },
+                failed_visits: 0,
+            },
+        ));
+        let follower = world.spawn((
+            Position { x: 2.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Caste::Worker,
+        ));
+        recruitment_system(&mut world, &config, &mut stats, &mut events);
+        world.despawn(leader).unwrap();
+
+        // 2. Action
+        recruitment_system(&mut world, &config, &mut stats, &mut events);
+
+        // 3. Assertion
+        assert!(world.get::<&TandemFollower>(follower).is_err());
+        assert!(world.get::<&RouteMemory>(follower).is_err());
+        assert_eq!(
+            *world.get::<&AntState>(follower).unwrap(),
+            AntState::Wandering
+        );
+        assert_eq!(stats.tandem_runs_completed, 0);
+    }
+
+    #[test]
+    fn test_tandem_run_system_holds_leader_for_lagging_follower() {
+        // 1. Setup
+        let mut world = World::new();
+        let config = SimulationConfig::default();
+        let leader = world.spawn((
+            Position { x: 20.0, y: 0.0 },
+            Velocity { dx: 1.0, dy: 0.0 },
+            AntState::ReturningToFood,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            TandemLeader,
+        ));
+        world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            Velocity { dx: 0.0, dy: 0.0 },
+            AntState::Following,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            TandemFollower,
+            Target(leader),
+        ));
+
+        // 2. Action
+        tandem_run_system(&mut world, &config);
+
+        // 3. Assertion
+        let vel = world.get::<&Velocity>(leader).unwrap();
+        assert_eq!((vel.dx, vel.dy), (0.0, 0.0));
+    }
+}
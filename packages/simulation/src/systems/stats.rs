@@ -1,8 +1,10 @@
-use crate::components::world::{Ant, AntState, FoodSource, Nest, Position};
+use crate::components::world::{
+    Ant, AntState, Brood, BroodStage, Caste, FoodSource, Nest, Position, Queen, Role,
+};
 use crate::engine::stats::Stats;
 use hecs::World;
 
-pub fn update_world_stats(world: &mut World, stats: &mut Stats) {
+pub fn update_world_stats(world: &mut World, stats: &mut Stats) -> u32 {
     let food_sources = world.query::<(&Position, &FoodSource)>().iter().count();
     let alive_ants = world
         .query::<(&Position, &Ant, &AntState)>()
@@ -13,7 +15,44 @@ pub fn update_world_stats(world: &mut World, stats: &mut Stats) {
     stats.alive_ants = alive_ants as u32;
     stats.food_sources = food_sources as u32;
 
+    stats.eggs = 0;
+    stats.larvae = 0;
+    stats.pupae = 0;
+    for (_, brood) in world.query::<&Brood>().iter() {
+        match brood.stage {
+            BroodStage::Egg => stats.eggs += 1,
+            BroodStage::Larva => stats.larvae += 1,
+            BroodStage::Pupa => stats.pupae += 1,
+        }
+    }
+    stats.resting_ants = world
+        .query::<&AntState>()
+        .with::<&Ant>()
+        .iter()
+        .filter(|(_, state)| **state == AntState::Resting)
+        .count() as u32;
+
+    stats.nurses = 0;
+    stats.foragers = 0;
+    for (_, (state, caste, role)) in world
+        .query::<(&AntState, Option<&Caste>, Option<&Role>)>()
+        .with::<&Ant>()
+        .iter()
+    {
+        if matches!(state, AntState::Dying(_))
+            || caste.copied().unwrap_or_default() != Caste::Worker
+        {
+            continue;
+        }
+        match role.copied().unwrap_or_default() {
+            Role::Nurse => stats.nurses += 1,
+            Role::Forager => stats.foragers += 1,
+        }
+    }
+    stats.queen_alive = world.query::<&Queen>().iter().next().is_some();
+
     if let Some((_, nest)) = world.query::<&Nest>().iter().next() {
         stats.food_in_nest = nest.food_store;
     }
+    world.len()
 }
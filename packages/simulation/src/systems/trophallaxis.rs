@@ -0,0 +1,300 @@
+use crate::components::world::{
+    Ant, AntState, FoodPayload, Position, RouteMemory, Satiety, Target,
+};
+use crate::engine::config::SimulationConfig;
+use crate::engine::events::{Events, SimulationEvent};
+use crate::engine::stats::Stats;
+use crate::utils::maths::target_distance_sq;
+use hecs::{Entity, World};
+use std::collections::{HashMap, HashSet};
+
+/// Pairs of ants within `radius` of each other. Ants are bucketed into a grid of
+/// `radius`-sized cells so only neighbouring cells are compared. Pairs come out in
+/// the order the ants were given, so the result is deterministic. Cell coordinates
+/// saturate rather than overflow, so a tiny radius only makes the grid coarser at its
+/// edges.
+pub fn ant_encounters(ants: &[(Entity, Position)], radius: f32) -> Vec<(Entity, Entity)> {
+    let radius_sq = radius.powi(2);
+    let cell_of = |pos: &Position| {
+        (
+            (pos.x / radius).floor() as i64,
+            (pos.y / radius).floor() as i64,
+        )
+    };
+
+    let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (index, (_, pos)) in ants.iter().enumerate() {
+        grid.entry(cell_of(pos)).or_default().push(index);
+    }
+
+    let mut encounters = Vec::new();
+    for (index, (entity, pos)) in ants.iter().enumerate() {
+        let (cell_x, cell_y) = cell_of(pos);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                let cell = (cell_x.saturating_add(dx), cell_y.saturating_add(dy));
+                let Some(neighbours) = grid.get(&cell) else {
+                    continue;
+                };
+                for &other in neighbours.iter().filter(|&&other| other > index) {
+                    let (other_entity, other_pos) = ants[other];
+                    if target_distance_sq(pos.x, pos.y, other_pos.x, other_pos.y) <= radius_sq {
+                        encounters.push((*entity, other_entity));
+                    }
+                }
+            }
+        }
+    }
+    encounters
+}
+
+/// Lets a hungry ant that meets a fed nestmate be fed mouth to mouth. A donor carrying
+/// food hands over one unit of its load; otherwise a donor above `donor_threshold`
+/// gives up `share_fraction` of the difference in satiety. Each ant takes part in at
+/// most one exchange per tick.
+pub fn trophallaxis_system(
+    world: &mut World,
+    config: &SimulationConfig,
+    stats: &mut Stats,
+    events: &mut Events,
+) -> u32 {
+    let trophallaxis = &config.trophallaxis;
+    if !trophallaxis.trophallaxis {
+        return 0;
+    }
+    let hunger = &config.hunger;
+
+    let ants: Vec<(Entity, Position)> = world
+        .query::<(&Position, &AntState)>()
+        .with::<(&Ant, &Satiety)>()
+        .iter()
+        .filter(|(_, (_, state))| !matches!(state, AntState::Dying(_)))
+        .map(|(entity, (pos, _))| (entity, *pos))
+        .collect();
+
+    let mut exchanged = HashSet::new();
+    for (first, second) in ant_encounters(&ants, trophallaxis.encounter_radius) {
+        if exchanged.contains(&first) || exchanged.contains(&second) {
+            continue;
+        }
+        let (Ok(first_satiety), Ok(second_satiety)) = (
+            world.get::<&Satiety>(first).map(|s| s.0),
+            world.get::<&Satiety>(second).map(|s| s.0),
+        ) else {
+            continue;
+        };
+        let (donor, receiver, donor_satiety, receiver_satiety) = if first_satiety <= second_satiety
+        {
+            (second, first, second_satiety, first_satiety)
+        } else {
+            (first, second, first_satiety, second_satiety)
+        };
+        if receiver_satiety >= hunger.hungry_threshold {
+            continue;
+        }
+
+        let load = world
+            .get::<&FoodPayload>(donor)
+            .map_or(0, |payload| payload.0);
+        let amount = if load > 0 {
+            if let Ok(mut payload) = world.get::<&mut FoodPayload>(donor) {
+                payload.0 -= 1;
+            }
+            if load == 1 {
+                empty_handed(world, donor);
+            }
+            stats.food_shared += 1;
+            hunger.satiety_per_food
+        } else if donor_satiety >= trophallaxis.donor_threshold {
+            let given = (donor_satiety - receiver_satiety) * trophallaxis.share_fraction;
+            if let Ok(mut satiety) = world.get::<&mut Satiety>(donor) {
+                satiety.0 -= given;
+            }
+            given
+        } else {
+            continue;
+        };
+
+        let gained = amount.min(hunger.max_satiety - receiver_satiety);
+        let receiver_satiety = receiver_satiety + gained;
+        if let Ok(mut satiety) = world.get::<&mut Satiety>(receiver) {
+            satiety.0 = receiver_satiety;
+        }
+        // A hungry ant that has been fed no longer needs to go home to eat
+        let was_hungry = world
+            .get::<&AntState>(receiver)
+            .is_ok_and(|state| *state == AntState::Hungry);
+        if was_hungry && receiver_satiety >= hunger.hungry_threshold {
+            world.insert_one(receiver, AntState::Wandering).ok();
+            world.remove_one::<Target>(receiver).ok();
+        }
+
+        exchanged.insert(donor);
+        exchanged.insert(receiver);
+        stats.trophallaxis_transfers += 1;
+        if let Ok(pos) = world.get::<&Position>(receiver) {
+            events.push(SimulationEvent::Trophallaxis {
+                donor: donor.id(),
+                receiver: receiver.id(),
+                amount: gained,
+                position: (*pos).into(),
+            });
+        }
+    }
+    ants.len() as u32
+}
+
+/// Sends a forager that has given away the last of its load off as if it had
+/// delivered it to the nest.
+fn empty_handed(world: &mut World, ant: Entity) {
+    let next_state = if world.get::<&RouteMemory>(ant).is_ok() {
+        AntState::ReturningToFood
+    } else {
+        AntState::Wandering
+    };
+    world.insert_one(ant, next_state).ok();
+    world.remove_one::<FoodPayload>(ant).ok();
+    world.remove_one::<Target>(ant).ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ant_encounters_finds_only_nearby_pairs() {
+        // 1. Setup
+        let mut world = World::new();
+        let a = world.spawn(());
+        let b = world.spawn(());
+        let c = world.spawn(());
+        let ants = [
+            (a, Position { x: 0.0, y: 0.0 }),
+            (b, Position { x: 1.5, y: 0.5 }),
+            (c, Position { x: 10.0, y: 0.0 }),
+        ];
+
+        // 2. Action
+        let encounters = ant_encounters(&ants, 2.0);
+
+        // 3. Assertion
+        assert_eq!(encounters, vec![(a, b)]);
+    }
+
+    #[test]
+    fn test_ant_encounters_survives_a_tiny_radius() {
+        // 1. Setup
+        let mut world = World::new();
+        let a = world.spawn(());
+        let b = world.spawn(());
+        let c = world.spawn(());
+        let ants = [
+            (a, Position { x: 0.0, y: 0.0 }),
+            (b, Position { x: 0.0, y: 0.0 }),
+            (
+                c,
+                Position {
+                    x: f32::MAX,
+                    y: -f32::MAX,
+                },
+            ),
+        ];
+
+        // 2. Action
+        let encounters = ant_encounters(&ants, f32::MIN_POSITIVE);
+
+        // 3. Assertion
+        assert_eq!(encounters, vec![(a, b)]);
+    }
+
+    #[test]
+    fn test_trophallaxis_system_shares_satiety_with_hungry_ant() {
+        // 1. Setup
+        let mut world = World::new();
+        let config = SimulationConfig::default();
+        let mut stats = Stats::default();
+        let mut events = Events::default();
+        let donor = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Satiety(90.0),
+        ));
+        let receiver = world.spawn((
+            Position { x: 1.0, y: 0.0 },
+            AntState::Hungry,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Satiety(10.0),
+        ));
+
+        // 2. Action
+        trophallaxis_system(&mut world, &config, &mut stats, &mut events);
+
+        // 3. Assertion
+        assert_eq!(world.get::<&Satiety>(donor).unwrap().0, 50.0);
+        assert_eq!(world.get::<&Satiety>(receiver).unwrap().0, 50.0);
+        assert_eq!(
+            *world.get::<&AntState>(receiver).unwrap(),
+            AntState::Wandering
+        );
+        assert_eq!(stats.trophallaxis_transfers, 1);
+        assert_eq!(stats.food_shared, 0);
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn test_trophallaxis_system_hands_over_carried_food() {
+        // 1. Setup
+        let mut world = World::new();
+        let config = SimulationConfig::default();
+        let mut stats = Stats::default();
+        let mut events = Events::default();
+        let donor = world.spawn((
+            Position { x: 0.0, y: 0.0 },
+            AntState::ReturningToNest,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Satiety(20.0),
+            FoodPayload(1),
+        ));
+        let receiver = world.spawn((
+            Position { x: 1.0, y: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Satiety(5.0),
+        ));
+        let bystander = world.spawn((
+            Position { x: 0.5, y: 0.0 },
+            AntState::Wandering,
+            Ant {
+                health: 100,
+                birth_tick: 0,
+            },
+            Satiety(1.0),
+        ));
+
+        // 2. Action
+        trophallaxis_system(&mut world, &config, &mut stats, &mut events);
+
+        // 3. Assertion
+        assert!(world.get::<&FoodPayload>(donor).is_err());
+        assert_eq!(*world.get::<&AntState>(donor).unwrap(), AntState::Wandering);
+        assert_eq!(
+            world.get::<&Satiety>(receiver).unwrap().0,
+            5.0 + config.hunger.satiety_per_food
+        );
+        assert_eq!(world.get::<&Satiety>(bystander).unwrap().0, 1.0);
+        assert_eq!(stats.food_shared, 1);
+    }
+}
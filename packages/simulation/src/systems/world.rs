@@ -1,11 +1,16 @@
 use crate::components::world::{AntState, FoodSource, Nest, PheromoneDeposit, Position, Velocity};
-use crate::engine::config::SIM_CONFIG;
+use crate::engine::config::SimulationConfig;
+use crate::engine::error::SimulationError;
+use crate::engine::events::{Events, SimulationEvent};
+use crate::engine::simulation::SimulationOptions;
 use crate::utils::maths::target_distance_sq;
 use hecs::World;
 use rand::Rng;
 
-pub fn enforce_bounds_system(world: &mut World, width: f32, height: f32) {
+pub fn enforce_bounds_system(world: &mut World, width: f32, height: f32) -> u32 {
+    let mut touched = 0;
     for (_entity, (pos, vel)) in world.query_mut::<(&mut Position, &mut Velocity)>() {
+        touched += 1;
         if pos.x < 0.0 {
             pos.x = 0.0;
             vel.dx = -vel.dx;
@@ -22,47 +27,64 @@ pub fn enforce_bounds_system(world: &mut World, width: f32, height: f32) {
             vel.dy = -vel.dy;
         }
     }
+    touched
 }
 
 pub fn food_spawn_system(
     world: &mut World,
-    world_width: f32,
-    world_height: f32,
-    max_food_sources: u32,
+    config: &SimulationConfig,
+    options: &SimulationOptions,
+    tick: u32,
+    events: &mut Events,
     rng: &mut impl Rng,
-) {
+) -> Result<u32, SimulationError> {
     let nest_pos = world
         .query::<(&Position, &Nest)>()
         .iter()
         .next()
         .map(|(_, (pos, _))| *pos)
-        .expect("No nest found when spawing food in food_spawn_system");
+        .ok_or(SimulationError::MissingNest)?;
 
     let food_source_count = world.query::<(&Position, &FoodSource)>().iter().count() as u32;
 
-    if food_source_count < max_food_sources && rng.random_bool(SIM_CONFIG.world.food_spawn_chance) {
+    if food_source_count < options.max_food_sources
+        && rng.random_bool(config.world.food_spawn_chance)
+    {
         let mut x;
         let mut y;
         loop {
-            x = rng.random_range(0.0..world_width);
-            y = rng.random_range(0.0..world_height);
+            x = rng.random_range(0.0..options.width);
+            y = rng.random_range(0.0..options.height);
             let distance_sq = target_distance_sq(nest_pos.x, nest_pos.y, x, y);
             // Ensure the food source is not too close to the nest
-            if distance_sq > SIM_CONFIG.world.food_spawn_min_distance_to_nest.powi(2) {
+            if distance_sq > config.world.food_spawn_min_distance_to_nest.powi(2) {
                 break;
             }
         }
-        world.spawn((Position { x, y }, FoodSource { amount: 100 }));
+        let position = Position { x, y };
+        let food_source = FoodSource {
+            amount: 100,
+            birth_tick: tick,
+        };
+        events.push(SimulationEvent::FoodSourceSpawned {
+            food_source: world.spawn((position, food_source.clone())).id(),
+            amount: food_source.amount,
+            position: position.into(),
+        });
     }
+
+    Ok(food_source_count)
 }
 
-pub fn despawn_system(world: &mut World) {
+pub fn despawn_system(world: &mut World, events: &mut Events) -> Result<u32, SimulationError> {
     let mut to_despawn = Vec::new();
+    let mut touched = 0;
 
     for (entity, (_, food_entity, pheromone_entity)) in world
         .query::<(&Position, Option<&FoodSource>, Option<&PheromoneDeposit>)>()
         .iter()
     {
+        touched += 1;
         if let Some(food_source) = food_entity
             && food_source.amount == 0
         {
@@ -77,11 +99,15 @@ pub fn despawn_system(world: &mut World) {
     }
 
     // Find all ants in the Dying state
-    for (entity, state) in world.query_mut::<&mut AntState>() {
+    for (entity, (state, pos)) in world.query_mut::<(&mut AntState, &Position)>() {
         if let AntState::Dying(ticks) = state {
             *ticks = ticks.saturating_sub(1);
             if *ticks == 0 {
                 to_despawn.push(entity);
+                events.push(SimulationEvent::AntDespawned {
+                    ant: entity.id(),
+                    position: (*pos).into(),
+                });
             }
         }
     }
@@ -89,8 +115,10 @@ pub fn despawn_system(world: &mut World) {
     for entity in to_despawn {
         world
             .despawn(entity)
-            .expect("Failed to despawn food entity in despawn_food_system");
+            .map_err(|_| SimulationError::MissingEntity(entity.id()))?;
     }
+
+    Ok(touched)
 }
 
 #[cfg(test)]
@@ -135,6 +163,10 @@ mod tests {
     #[test]
     fn test_food_spawn_system_spawns_food_at_random_positions() {
         // 1. Setup
+        let options = SimulationOptions {
+            max_food_sources: 100,
+            ..SimulationOptions::default()
+        };
         let mut rng = StdRng::seed_from_u64(42);
         let mut world = World::new();
 
@@ -142,7 +174,15 @@ mod tests {
 
         // 2. Action
         for _ in 0..500 {
-            food_spawn_system(&mut world, 100.0, 100.0, 100, &mut rng);
+            food_spawn_system(
+                &mut world,
+                &SimulationConfig::default(),
+                &options,
+                0,
+                &mut Events::default(),
+                &mut rng,
+            )
+            .unwrap();
         }
 
         // 3. Assertion
@@ -150,14 +190,38 @@ mod tests {
         assert!(food_count > 1);
     }
 
+    #[test]
+    fn test_food_spawn_system_returns_error_without_nest() {
+        let options = SimulationOptions::default();
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut world = World::new();
+
+        let result = food_spawn_system(
+            &mut world,
+            &SimulationConfig::default(),
+            &options,
+            0,
+            &mut Events::default(),
+            &mut rng,
+        );
+
+        assert_eq!(result, Err(SimulationError::MissingNest));
+    }
+
     #[test]
     fn test_despawn_system_removes_depleted_food_sources() {
         // 1. Setup
         let mut world = World::new();
-        let food_entity = world.spawn((Position { x: 10.0, y: 10.0 }, FoodSource { amount: 0 }));
+        let food_entity = world.spawn((
+            Position { x: 10.0, y: 10.0 },
+            FoodSource {
+                amount: 0,
+                birth_tick: 0,
+            },
+        ));
 
         // 2. Action
-        despawn_system(&mut world);
+        despawn_system(&mut world, &mut Events::default()).unwrap();
 
         // 3. Assertion
         assert!(world.get::<&FoodSource>(food_entity).is_err());
@@ -174,7 +238,7 @@ mod tests {
         ));
 
         // 2. Action
-        despawn_system(&mut world);
+        despawn_system(&mut world, &mut Events::default()).unwrap();
 
         // 3. Assertion
         assert!(world.get::<&PheromoneDeposit>(pheromone_entity).is_err());
@@ -0,0 +1,56 @@
+use std::hash::Hasher;
+use std::io;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit FNV-1a. Unlike `DefaultHasher` its output is fixed across Rust versions and
+/// platforms, so hashes can be stored and compared later.
+pub struct Fnv1aHasher(u64);
+
+impl Default for Fnv1aHasher {
+    fn default() -> Self {
+        Fnv1aHasher(FNV_OFFSET_BASIS)
+    }
+}
+
+impl Hasher for Fnv1aHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Lets serializers stream straight into the hash without building a buffer first.
+impl io::Write for Fnv1aHasher {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        Hasher::write(self, bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a_matches_reference_values() {
+        let mut empty = Fnv1aHasher::default();
+        empty.write(b"");
+        let mut text = Fnv1aHasher::default();
+        text.write(b"a");
+
+        assert_eq!(empty.finish(), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(text.finish(), 0xaf63_dc4c_8601_ec8c);
+    }
+}
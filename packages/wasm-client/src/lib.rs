@@ -1,4 +1,5 @@
-use simulation::{Simulation, SimulationOptions};
+use simulation::engine::config::ConfigError;
+use simulation::{Simulation, SimulationError, SimulationErrorDto, SimulationOptions};
 use wasm_bindgen::prelude::*;
 
 // Provides better error messages in the browser console when a panic occurs.
@@ -8,6 +9,13 @@ pub fn set_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
+/// Converts a simulation error into a `{ kind, message }` object for the web client.
+fn to_js_error(error: impl Into<SimulationError>) -> JsValue {
+    let error = error.into();
+    serde_wasm_bindgen::to_value(&SimulationErrorDto::from(&error))
+        .unwrap_or_else(|_| JsValue::from_str(&error.to_string()))
+}
+
 #[wasm_bindgen]
 pub struct WasmSimulation {
     simulation: Simulation,
@@ -17,25 +25,141 @@ pub struct WasmSimulation {
 impl WasmSimulation {
     #[wasm_bindgen(constructor)]
     pub fn new(options: JsValue) -> Result<WasmSimulation, JsValue> {
-        let sim_options: SimulationOptions = serde_wasm_bindgen::from_value(options)
-            .map_err(|e| JsValue::from_str(&format!("Failed to parse SimulationOptions: {}", e)))?;
+        let sim_options: SimulationOptions =
+            serde_wasm_bindgen::from_value(options).map_err(|e| {
+                to_js_error(SimulationError::InvalidOptions(format!(
+                    "Failed to parse SimulationOptions: {}",
+                    e
+                )))
+            })?;
 
-        let simulation = Simulation::new(sim_options);
+        let simulation = Simulation::new(sim_options).map_err(to_js_error)?;
         Ok(WasmSimulation { simulation })
     }
 
+    /// Restores a simulation from a snapshot written by `save`.
+    #[wasm_bindgen]
+    pub fn load(snapshot: &str) -> Result<WasmSimulation, JsValue> {
+        let simulation = Simulation::load(snapshot).map_err(to_js_error)?;
+        Ok(WasmSimulation { simulation })
+    }
+
+    /// Serializes the complete simulation state so it can be restored later.
+    #[wasm_bindgen]
+    pub fn save(&self) -> Result<String, JsValue> {
+        self.simulation.save().map_err(to_js_error)
+    }
+
+    #[wasm_bindgen]
+    pub fn tick(&mut self) -> Result<(), JsValue> {
+        self.simulation.tick().map_err(to_js_error)
+    }
+
+    /// Advances the simulation by `dt` seconds and returns a StepSummary.
+    #[wasm_bindgen]
+    pub fn tick_with_dt(&mut self, dt: f64) -> Result<JsValue, JsValue> {
+        let summary = self.simulation.tick_with_dt(dt).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&summary).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Runs `ticks` ticks in one call and returns a RunSummary.
+    #[wasm_bindgen]
+    pub fn tick_many(&mut self, ticks: u32) -> Result<JsValue, JsValue> {
+        let summary = self.simulation.run(ticks).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&summary).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Gets the config the simulation is currently running with.
+    #[wasm_bindgen]
+    pub fn get_config(&self) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(self.simulation.config())
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Merges a full or partial SimulationConfig into the running simulation.
+    #[wasm_bindgen]
+    pub fn update_config(&mut self, patch: JsValue) -> Result<(), JsValue> {
+        let patch: serde_json::Value = serde_wasm_bindgen::from_value(patch)
+            .map_err(|e| to_js_error(ConfigError::Parse(e.to_string())))?;
+
+        self.simulation.patch_config(patch).map_err(to_js_error)
+    }
+
+    /// Starts recording the session so it can be replayed exactly. Must be called before
+    /// the first tick.
     #[wasm_bindgen]
-    pub fn tick(&mut self) {
-        self.simulation.tick();
+    pub fn start_recording(&mut self, hash_interval: u32) -> Result<(), JsValue> {
+        self.simulation
+            .start_recording(hash_interval)
+            .map_err(to_js_error)
+    }
+
+    /// Stops recording and returns the command log as JSON, if recording was on.
+    #[wasm_bindgen]
+    pub fn stop_recording(&mut self) -> Result<Option<String>, JsValue> {
+        self.simulation
+            .stop_recording()
+            .map(|log| log.to_json())
+            .transpose()
+            .map_err(to_js_error)
+    }
+
+    /// Keeps keyframes every `keyframe_interval` ticks so the simulation can be rewound.
+    #[wasm_bindgen]
+    pub fn enable_rewind(&mut self, keyframe_interval: u32, max_keyframes: usize) {
+        self.simulation
+            .enable_rewind(keyframe_interval, max_keyframes);
+    }
+
+    /// Goes back one tick.
+    #[wasm_bindgen]
+    pub fn step_back(&mut self) -> Result<(), JsValue> {
+        self.simulation.step_back().map_err(to_js_error)
+    }
+
+    /// Moves to `tick`, re-simulating from the nearest keyframe if it is in the past.
+    #[wasm_bindgen]
+    pub fn seek(&mut self, tick: u32) -> Result<(), JsValue> {
+        self.simulation.seek(tick).map_err(to_js_error)
+    }
+
+    /// Lists the scheduled systems in the order they run.
+    #[wasm_bindgen]
+    pub fn system_names(&self) -> Vec<String> {
+        self.simulation
+            .schedule()
+            .system_names()
+            .map(String::from)
+            .collect()
+    }
+
+    /// Turns a scheduled system on or off by name.
+    #[wasm_bindgen]
+    pub fn set_system_enabled(&mut self, name: &str, enabled: bool) -> Result<(), JsValue> {
+        self.simulation
+            .set_system_enabled(name, enabled)
+            .map_err(to_js_error)
+    }
+
+    /// Takes the events raised since the last call.
+    #[wasm_bindgen]
+    pub fn drain_events(&mut self) -> Result<JsValue, JsValue> {
+        let events = self.simulation.drain_events();
+        serde_wasm_bindgen::to_value(&events).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Gets per-system timings over the last few ticks.
+    #[cfg(feature = "profiling")]
+    #[wasm_bindgen]
+    pub fn get_profile(&self) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(&self.simulation.profile())
+            .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
     /// Gets the current state of the world.
     #[wasm_bindgen]
     pub fn get_world_state(&mut self) -> Result<JsValue, JsValue> {
-        let world_dto = self
-            .simulation
-            .get_world_state_dto()
-            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let world_dto = self.simulation.get_world_state_dto().map_err(to_js_error)?;
         serde_wasm_bindgen::to_value(&world_dto).map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
@@ -45,7 +169,7 @@ impl WasmSimulation {
         let stats_dto = self
             .simulation
             .get_world_statistics_dto()
-            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+            .map_err(to_js_error)?;
         serde_wasm_bindgen::to_value(&stats_dto).map_err(|e| JsValue::from_str(&e.to_string()))
     }
 }